@@ -0,0 +1,346 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use semver::{Version, VersionReq};
+use toml_edit::{value, DocumentMut, Item, TableLike};
+
+pub static MANIFEST_FILE_NAME: &str = "Cargo.toml";
+
+/// Dependency tables whose crates must be released before the dependent crate
+static DEPENDENCY_TABLES: [&str; 2] = ["dependencies", "build-dependencies"];
+
+/// Dependency tables whose version requirements are kept in sync with released crates
+static REQUIREMENT_TABLES: [&str; 3] = ["dependencies", "build-dependencies", "dev-dependencies"];
+
+/// Member crate of a Cargo workspace
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    pub name: String,
+    /// Crate directory relative to the workspace root
+    pub path: PathBuf,
+}
+impl WorkspaceMember {
+    pub fn manifest(&self) -> PathBuf {
+        self.path.join(MANIFEST_FILE_NAME)
+    }
+}
+
+/// Reads the Cargo manifest, `None` if it does not exist
+pub fn read_manifest(manifest: &Path) -> Option<DocumentMut> {
+    let content = fs::read_to_string(manifest).ok()?;
+    match content.parse::<DocumentMut>() {
+        Ok(document) => Some(document),
+        Err(e) => {
+            eprintln!("Issue parsing {}: {}!", manifest.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn write_manifest(manifest: &Path, document: &DocumentMut) {
+    if let Err(e) = fs::write(manifest, document.to_string()) {
+        eprintln!("Issue writing {}: {}!", manifest.display(), e);
+        std::process::exit(1);
+    }
+}
+
+/// Package name declared in the manifest
+pub fn package_name(manifest: &Path) -> Option<String> {
+    let document = read_manifest(manifest)?;
+    Some(document.get("package")?.get("name")?.as_str()?.to_string())
+}
+
+/// Checks whether the manifest inherits its version with `version.workspace = true`
+pub fn inherits_version(manifest: &Path) -> bool {
+    read_manifest(manifest)
+        .and_then(|document| {
+            document
+                .get("package")?
+                .get("version")?
+                .get("workspace")?
+                .as_bool()
+        })
+        .unwrap_or_default()
+}
+
+/// Package version of the manifest, `read` provides the content of manifests relative to the
+/// workspace root. Inherited versions are taken from `[workspace.package]` of the root manifest
+pub fn manifest_version(
+    manifest: &Path,
+    read: impl Fn(&Path) -> Option<String>,
+) -> Option<Version> {
+    let document = read(manifest)?.parse::<DocumentMut>().ok()?;
+    let version = document.get("package")?.get("version")?;
+    if let Some(version) = version.as_str() {
+        return Version::parse(version).ok();
+    }
+    if !version.get("workspace")?.as_bool()? {
+        return None;
+    }
+    let workspace = read(Path::new(MANIFEST_FILE_NAME))?
+        .parse::<DocumentMut>()
+        .ok()?;
+    Version::parse(
+        workspace
+            .get("workspace")?
+            .get("package")?
+            .get("version")?
+            .as_str()?,
+    )
+    .ok()
+}
+
+/// Matches a single path segment against a glob pattern supporting `*` and `?`
+fn matches_segment(pattern: &str, segment: &str) -> bool {
+    let pattern = regex::escape(pattern)
+        .replace(r"\*", "[^/]*")
+        .replace(r"\?", "[^/]");
+    Regex::new(&format!("^{}$", pattern)).is_ok_and(|regex| regex.is_match(segment))
+}
+
+/// Expands a `workspace.members` entry to the crate directories it matches
+fn expand_member(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut directories = vec![PathBuf::new()];
+    for segment in Path::new(pattern).iter() {
+        let segment = segment.to_string_lossy();
+        if !segment.contains(['*', '?']) {
+            directories.iter_mut().for_each(|dir| dir.push(&*segment));
+            continue;
+        }
+        directories = directories
+            .iter()
+            .flat_map(|dir| {
+                let mut children: Vec<PathBuf> = fs::read_dir(root.join(dir))
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .filter(|entry| matches_segment(&segment, &entry.file_name().to_string_lossy()))
+                    .map(|entry| dir.join(entry.file_name()))
+                    .collect();
+                children.sort();
+                children
+            })
+            .collect();
+    }
+    directories
+        .into_iter()
+        .filter(|dir| root.join(dir).join(MANIFEST_FILE_NAME).is_file())
+        .collect()
+}
+
+/// Members of the workspace whose manifest is in the root directory, empty if there is none
+pub fn workspace_members(root: &Path) -> Vec<WorkspaceMember> {
+    let document = match read_manifest(&root.join(MANIFEST_FILE_NAME)) {
+        Some(document) => document,
+        None => return Vec::new(),
+    };
+    let workspace = match document.get("workspace") {
+        Some(workspace) => workspace,
+        None => return Vec::new(),
+    };
+    let patterns = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(Item::as_array)
+            .map(|array| {
+                array
+                    .iter()
+                    .filter_map(|pattern| pattern.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let excluded: Vec<PathBuf> = patterns("exclude")
+        .iter()
+        .flat_map(|pattern| expand_member(root, pattern))
+        .collect();
+
+    let mut members: Vec<WorkspaceMember> = Vec::new();
+    for path in patterns("members")
+        .iter()
+        .flat_map(|pattern| expand_member(root, pattern))
+    {
+        if excluded.contains(&path) || members.iter().any(|member| member.path == path) {
+            continue;
+        }
+        if let Some(name) = package_name(&root.join(&path).join(MANIFEST_FILE_NAME)) {
+            members.push(WorkspaceMember { name, path });
+        }
+    }
+    members
+}
+
+/// Directory of a `path` dependency, inherited dependencies are looked up in the workspace
+/// manifest of the root directory
+fn dependency_path(
+    name: &str,
+    dependency: &Item,
+    directory: &Path,
+    root: &Path,
+) -> Option<PathBuf> {
+    if let Some(path) = dependency.get("path").and_then(Item::as_str) {
+        return Some(directory.join(path));
+    }
+    if !dependency
+        .get("workspace")
+        .and_then(Item::as_bool)
+        .unwrap_or_default()
+    {
+        return None;
+    }
+    let workspace = read_manifest(&root.join(MANIFEST_FILE_NAME))?;
+    let path = workspace
+        .get("workspace")?
+        .get("dependencies")?
+        .get(name)?
+        .get("path")?
+        .as_str()?;
+    Some(root.join(path))
+}
+
+/// Directories of the crates the manifest depends on through `path` dependencies
+pub fn path_dependencies(manifest: &Path, root: &Path) -> Vec<PathBuf> {
+    let document = match read_manifest(manifest) {
+        Some(document) => document,
+        None => return Vec::new(),
+    };
+    let directory = manifest.parent().unwrap_or(Path::new("."));
+    DEPENDENCY_TABLES
+        .iter()
+        .filter_map(|table| document.get(table).and_then(Item::as_table_like))
+        .flat_map(|table| table.iter())
+        .filter_map(|(name, dependency)| dependency_path(name, dependency, directory, root))
+        .collect()
+}
+
+/// Replaces a string value, keeping surrounding whitespace and comments
+fn set_string(item: &mut Item, text: String) {
+    let decor = item.as_value().map(|old| old.decor().clone());
+    *item = value(text);
+    if let (Some(decor), Some(new)) = (decor, item.as_value_mut()) {
+        *new.decor_mut() = decor;
+    }
+}
+
+/// Replaces the version of a caret, tilde, exact or bare requirement, keeping its operator. Other
+/// requirements (several comparators, `<`, `>` or wildcards) are kept as they are, `None` if they
+/// do not match the version
+fn update_requirement(requirement: &str, version: &Version) -> Option<String> {
+    let requirement = requirement.trim();
+    if requirement.contains([',', '<', '>', '*']) {
+        return VersionReq::parse(requirement)
+            .is_ok_and(|requirement| requirement.matches(version))
+            .then(|| requirement.to_string());
+    }
+    let operator: String = requirement
+        .chars()
+        .take_while(|c| matches!(c, '^' | '~' | '='))
+        .collect();
+    Some(format!("{}{}", operator, version))
+}
+
+/// Updates version requirements of the released crates in a dependency table, returns whether
+/// anything changed
+fn update_dependencies(table: &mut dyn TableLike, released: &[(String, Version)]) -> bool {
+    let mut changed = false;
+    for (key, dependency) in table.iter_mut() {
+        let dependency = match dependency.as_table_like_mut() {
+            Some(dependency) => dependency,
+            // Plain version strings refer to registry crates
+            None => continue,
+        };
+        let name = dependency
+            .get("package")
+            .and_then(Item::as_str)
+            .unwrap_or(key.get())
+            .to_string();
+        let version = match released.iter().find(|(crate_name, _)| *crate_name == name) {
+            Some((_, version)) => version,
+            None => continue,
+        };
+        if let Some(requirement) = dependency.get_mut("version") {
+            let current = requirement.as_str().unwrap_or_default().to_string();
+            match update_requirement(&current, version) {
+                Some(updated) if updated != current => {
+                    set_string(requirement, updated);
+                    changed = true;
+                }
+                Some(_) => {}
+                None => eprintln!(
+                    "Warning: requirement \"{}\" on crate \"{}\" does not match {}, not updated!",
+                    current, name, version
+                ),
+            }
+        }
+    }
+    changed
+}
+
+/// Sets the package version of the manifest, inherited versions are left to
+/// `set_workspace_version`
+pub fn set_package_version(manifest: &Path, version: &Version) {
+    let mut document = match read_manifest(manifest) {
+        Some(document) => document,
+        None => return,
+    };
+    let package = match document
+        .get_mut("package")
+        .and_then(Item::as_table_like_mut)
+    {
+        Some(package) => package,
+        None => return,
+    };
+    match package.get_mut("version") {
+        Some(item) if item.is_str() => set_string(item, version.to_string()),
+        Some(_) => return,
+        None => {
+            package.insert("version", value(version.to_string()));
+        }
+    }
+    write_manifest(manifest, &document);
+}
+
+/// Sets the version members inherit from `[workspace.package]` of the workspace manifest
+pub fn set_workspace_version(manifest: &Path, version: &Version) {
+    let mut document = match read_manifest(manifest) {
+        Some(document) => document,
+        None => return,
+    };
+    let item = document
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("package"))
+        .and_then(|package| package.get_mut("version"));
+    if let Some(item) = item {
+        set_string(item, version.to_string());
+        write_manifest(manifest, &document);
+    }
+}
+
+/// Updates version requirements on the released crates in all dependency tables of the manifest,
+/// including `[workspace.dependencies]`
+pub fn update_requirements(manifest: &Path, released: &[(String, Version)]) {
+    let mut document = match read_manifest(manifest) {
+        Some(document) => document,
+        None => return,
+    };
+    let mut changed = false;
+    for table in REQUIREMENT_TABLES {
+        if let Some(table) = document.get_mut(table).and_then(Item::as_table_like_mut) {
+            changed |= update_dependencies(table, released);
+        }
+    }
+    let workspace = document
+        .get_mut("workspace")
+        .and_then(|workspace| workspace.get_mut("dependencies"))
+        .and_then(Item::as_table_like_mut);
+    if let Some(table) = workspace {
+        changed |= update_dependencies(table, released);
+    }
+    if changed {
+        write_manifest(manifest, &document);
+    }
+}
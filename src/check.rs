@@ -0,0 +1,47 @@
+use clap::ValueEnum as _;
+use serde_json::json;
+
+use crate::{
+    cli::Format,
+    commits::{commits_since, release_range, required_release},
+    default,
+    settings::Settings,
+};
+
+/// Exits successfully if the commits since the latest tag require a release, with
+/// `NO_RELEASE_EXIT_CODE` otherwise. Optionally prints the required bump level
+pub fn check(settings: &Settings, print_bump: bool) {
+    let (repo, from, target) = release_range(settings, None, None);
+    let range = match commits_since(&repo, &settings.commits, from, target) {
+        Ok(range) => range,
+        Err(e) => {
+            eprintln!("Issue reading commits: {}!", e.message());
+            std::process::exit(1);
+        }
+    };
+    // Like `next`, a `Release-As` trailer requests a release regardless of the commit types
+    let (requested, bump) = required_release(&range.commits, &settings.commits);
+    let bump = bump.map(|bump| bump.to_possible_value().unwrap().get_name().to_string());
+    let release = bump.is_some() || requested.is_some();
+
+    if print_bump {
+        match settings.output.format {
+            Format::Plain => {
+                if let Some(bump) = &bump {
+                    println!("{}", bump);
+                }
+            }
+            Format::Json => {
+                let value = json!({
+                    "release": release,
+                    "bump": bump,
+                    "release_as": requested.as_ref().map(|version| version.to_string()),
+                });
+                println!("{}", serde_json::to_string_pretty(&value).unwrap());
+            }
+        }
+    }
+    if !release {
+        std::process::exit(default::NO_RELEASE_EXIT_CODE);
+    }
+}
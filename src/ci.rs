@@ -0,0 +1,123 @@
+use std::env;
+
+/// Information about the build recovered from the CI provider's environment variables
+#[derive(Debug, Default, Clone)]
+pub struct CiEnvironment {
+    /// Short provider name (e.g., github, gitlab, ...)
+    pub provider: String,
+    pub branch: Option<String>,
+    pub pr: Option<String>,
+    pub build_number: Option<String>,
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn is_set(name: &str) -> bool {
+    var(name).is_some()
+}
+
+/// Detects supported CI provider from the environment
+pub fn detect() -> Option<CiEnvironment> {
+    if is_set("GITHUB_ACTIONS") {
+        Some(github_actions())
+    } else if is_set("GITLAB_CI") {
+        Some(gitlab_ci())
+    } else if is_set("JENKINS_URL") {
+        Some(jenkins())
+    } else if is_set("TF_BUILD") {
+        Some(azure_pipelines())
+    } else if is_set("BUILDKITE") {
+        Some(buildkite())
+    } else if is_set("CIRCLECI") {
+        Some(circleci())
+    } else {
+        None
+    }
+}
+
+fn github_actions() -> CiEnvironment {
+    let git_ref = var("GITHUB_REF").unwrap_or_default();
+    // Pull request refs look like `refs/pull/<number>/merge`
+    let pr = git_ref
+        .strip_prefix("refs/pull/")
+        .and_then(|rest| rest.split('/').next())
+        .map(|number| number.to_string());
+    let branch = var("GITHUB_HEAD_REF").or_else(|| {
+        git_ref
+            .strip_prefix("refs/heads/")
+            .map(|branch| branch.to_string())
+    });
+    CiEnvironment {
+        provider: "github".to_string(),
+        branch,
+        pr,
+        build_number: var("GITHUB_RUN_NUMBER"),
+    }
+}
+
+fn gitlab_ci() -> CiEnvironment {
+    CiEnvironment {
+        provider: "gitlab".to_string(),
+        branch: var("CI_MERGE_REQUEST_SOURCE_BRANCH_NAME").or_else(|| var("CI_COMMIT_BRANCH")),
+        pr: var("CI_MERGE_REQUEST_IID"),
+        build_number: var("CI_PIPELINE_IID"),
+    }
+}
+
+fn jenkins() -> CiEnvironment {
+    let branch = var("CHANGE_BRANCH")
+        .or_else(|| var("BRANCH_NAME"))
+        .or_else(|| {
+            var("GIT_BRANCH").map(|branch| match branch.strip_prefix("origin/") {
+                Some(branch) => branch.to_string(),
+                None => branch,
+            })
+        });
+    CiEnvironment {
+        provider: "jenkins".to_string(),
+        branch,
+        pr: var("CHANGE_ID"),
+        build_number: var("BUILD_NUMBER"),
+    }
+}
+
+fn azure_pipelines() -> CiEnvironment {
+    let branch = var("SYSTEM_PULLREQUEST_SOURCEBRANCH")
+        .or_else(|| var("BUILD_SOURCEBRANCH"))
+        .map(|branch| match branch.strip_prefix("refs/heads/") {
+            Some(branch) => branch.to_string(),
+            None => branch,
+        });
+    CiEnvironment {
+        provider: "azure".to_string(),
+        branch,
+        pr: var("SYSTEM_PULLREQUEST_PULLREQUESTNUMBER")
+            .or_else(|| var("SYSTEM_PULLREQUEST_PULLREQUESTID")),
+        build_number: var("BUILD_BUILDID"),
+    }
+}
+
+fn buildkite() -> CiEnvironment {
+    CiEnvironment {
+        provider: "buildkite".to_string(),
+        branch: var("BUILDKITE_BRANCH"),
+        // Set to `false` for builds which are not pull requests
+        pr: var("BUILDKITE_PULL_REQUEST").filter(|pr| pr != "false"),
+        build_number: var("BUILDKITE_BUILD_NUMBER"),
+    }
+}
+
+fn circleci() -> CiEnvironment {
+    let pr = var("CIRCLE_PR_NUMBER").or_else(|| {
+        var("CIRCLE_PULL_REQUEST")
+            .and_then(|url| url.rsplit('/').next().map(|number| number.to_string()))
+    });
+    CiEnvironment {
+        provider: "circleci".to_string(),
+        branch: var("CIRCLE_BRANCH"),
+        pr,
+        build_number: var("CIRCLE_BUILD_NUM"),
+    }
+}
@@ -25,6 +25,41 @@ pub struct Cli {
     )]
     pub config: Option<PathBuf>,
 
+    #[clap(
+        short,
+        long,
+        value_name = "REVSPEC",
+        help = "Evaluate version at given commit, branch or tag instead of HEAD"
+    )]
+    pub rev: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "Branch name to use instead of the checked out one (e.g., for detached HEAD)"
+    )]
+    pub branch: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "Monorepo component to version, selects its tags and commits"
+    )]
+    pub component: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "POLICY",
+        help = "Handling of shallow repositories [default: warn]"
+    )]
+    pub shallow: Option<ShallowPolicy>,
+
+    #[clap(
+        long,
+        help = "Let major bumps leave 0.x even if `zero_major_mode` is enabled"
+    )]
+    pub allow_major_from_zero: bool,
+
     #[clap(flatten, next_help_heading = "Filter options")]
     pub filter: FilterOptions,
 
@@ -51,6 +86,60 @@ pub enum Commands {
         /// Field/part of the version
         #[clap(short, long)]
         field: Option<Field>,
+
+        /// Use given version instead of bumping, pre-release and build metadata templates still apply
+        #[clap(long, value_name = "VERSION")]
+        set: Option<semver::Version>,
+    },
+    /// List commits since the latest version with their Conventional Commit classification
+    Log {
+        /// Start of the range (exclusive) [default: latest matching tag]
+        #[clap(long, value_name = "REVSPEC")]
+        from: Option<String>,
+
+        /// End of the range (inclusive) [default: --rev or HEAD]
+        #[clap(long, value_name = "REVSPEC")]
+        to: Option<String>,
+    },
+    /// Exit successfully if commits since the latest version require a release, with status 3 otherwise
+    Check {
+        /// Print the bump level required by the commits
+        #[clap(long)]
+        bump: bool,
+    },
+    /// Validate a commit message against the Conventional Commits grammar
+    LintCommit {
+        /// File containing the commit message, `-` for standard input
+        #[clap(value_name = "FILE|-")]
+        file: PathBuf,
+    },
+    /// Validate messages of all commits in a revision range (e.g., main..HEAD)
+    LintRange {
+        #[clap(value_name = "RANGE")]
+        range: String,
+    },
+    /// Plan releases of all monorepo components, dependents of released components are released too
+    Plan,
+    /// List current and next versions of all crates in the Cargo workspace
+    Workspace {
+        /// Update versions of released crates in member manifests and their internal dependency requirements
+        #[clap(long)]
+        write: bool,
+    },
+    /// Manage Git hooks
+    Hooks {
+        #[clap(subcommand)]
+        action: HooksAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HooksAction {
+    /// Install a `commit-msg` hook running `doxxer lint-commit`
+    Install {
+        /// Overwrite an existing hook
+        #[clap(long)]
+        force: bool,
     },
 }
 
@@ -86,7 +175,52 @@ pub enum Strategy {
     PrePatch(PreReleaseWithBumpArgs),
     /// Development version (non-standard)
     Dev(PrereleaseArgs),
+    /// Major, minor or patch version as required by the commits since the latest tag
+    Auto(StandardBumpArgs),
 }
+impl Strategy {
+    /// Creates strategy without any options from its subcommand name
+    pub fn from_name(name: &str) -> Option<Self> {
+        let standard = || StandardBumpArgs {
+            bump_options: BumpingOptions { increment: None },
+            build_metadata_options: BuildMetadataOptions {
+                build_metadata_template: None,
+            },
+        };
+        let prerelease = || PrereleaseArgs {
+            prerelease_options: PrereleaseOptions {
+                identifier: None,
+                prerelease_template: None,
+            },
+            build_metadata_options: BuildMetadataOptions {
+                build_metadata_template: None,
+            },
+        };
+        let prerelease_with_bump = || PreReleaseWithBumpArgs {
+            prerelease_options: PrereleaseOptions {
+                identifier: None,
+                prerelease_template: None,
+            },
+            bump_options: BumpingOptions { increment: None },
+            build_metadata_options: BuildMetadataOptions {
+                build_metadata_template: None,
+            },
+        };
+        match name {
+            "major" => Some(Strategy::Major(standard())),
+            "minor" => Some(Strategy::Minor(standard())),
+            "patch" => Some(Strategy::Patch(standard())),
+            "prerelease" => Some(Strategy::Prerelease(prerelease())),
+            "pre-major" => Some(Strategy::PreMajor(prerelease_with_bump())),
+            "pre-minor" => Some(Strategy::PreMinor(prerelease_with_bump())),
+            "pre-patch" => Some(Strategy::PrePatch(prerelease_with_bump())),
+            "dev" => Some(Strategy::Dev(prerelease())),
+            "auto" => Some(Strategy::Auto(standard())),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Args)]
 pub struct StandardBumpArgs {
     #[clap(flatten)]
@@ -140,8 +274,135 @@ pub struct BuildMetadataOptions {
 #[derive(Debug, Args)]
 #[group(required = false, multiple = true)]
 pub struct FilterOptions {
-    #[clap(short, long="tag-filter", value_name="REGEX",  help=format!("Regular expression for selecting relevant tags [default: {}]", default::TAG_FILTER))]
+    #[clap(short, long="tag-filter", value_name="REGEX",  help=format!("Regular expression for selecting relevant tags, optionally with a `version` capture group [default: {}]", default::TAG_FILTER))]
     pub tag: Option<String>,
+    #[clap(
+        long = "tag-template",
+        value_name = "TEMPLATE",
+        help = "Template for parsing versions from tags (e.g., \"{component}-v{version}\")"
+    )]
+    pub tag_template: Option<String>,
+    #[clap(
+        long = "exclude-tag",
+        value_name = "REGEX",
+        help = "Regular expression for ignoring tags, can be repeated"
+    )]
+    pub exclude: Option<Vec<String>>,
+    #[clap(
+        long = "version-filter",
+        value_name = "REQ",
+        help = "Version requirement tags must satisfy (e.g., \">=1.0, <2\")"
+    )]
+    pub version_requirement: Option<String>,
+    #[clap(
+        long = "prerelease-filter",
+        value_name = "MODE",
+        help = "Handling of pre-release tags [default: include]"
+    )]
+    pub prerelease: Option<PrereleaseFilter>,
+    #[clap(
+        long = "tag-conflict",
+        value_name = "POLICY",
+        help = "Handling of multiple tags for the same version [default: prefer-annotated]"
+    )]
+    pub conflict: Option<TagConflictPolicy>,
+    #[clap(
+        long = "tag-order",
+        value_name = "ORDER",
+        help = "Ordering for selecting the latest tag [default: semver]"
+    )]
+    pub order: Option<TagOrder>,
+    #[clap(
+        long = "annotated-only",
+        help = "Ignore lightweight tags [default: false]"
+    )]
+    pub annotated: bool,
+    #[clap(
+        long = "signed-only",
+        value_name = "FORMAT",
+        help = "Only use tags with a valid signature in given format"
+    )]
+    pub signed: Option<SignatureFormat>,
+}
+
+/// Accepted tag signature format
+#[derive(Debug, Clone, ValueEnum)]
+pub enum SignatureFormat {
+    Gpg,
+    Ssh,
+    Any,
+}
+
+/// Ordering for selecting the latest tag
+#[derive(Debug, Clone, ValueEnum)]
+pub enum TagOrder {
+    /// Highest SemVer precedence
+    Semver,
+    /// Nearest tag on the first-parent history, like `git describe`
+    Topology,
+    /// Most recently created tag
+    TaggerDate,
+}
+
+/// Handling of multiple tags for the same version
+#[derive(Debug, Clone, ValueEnum)]
+pub enum TagConflictPolicy {
+    PreferAnnotated,
+    PreferOldest,
+    Error,
+}
+
+/// Version part bumped for a change
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+pub enum BumpLevel {
+    Patch,
+    Minor,
+    Major,
+}
+
+/// Commit message convention used for inferring bump levels
+#[derive(Debug, Clone, ValueEnum)]
+pub enum CommitConvention {
+    /// Conventional Commits (`feat: ...`, `fix!: ...`)
+    Conventional,
+    /// Gitmoji (✨ minor, 🐛 patch, 💥 major)
+    Gitmoji,
+    /// Regular expressions configured in `commits.patterns`
+    Regex,
+}
+
+/// Parsing of squash merge commit messages
+#[derive(Debug, Clone, ValueEnum)]
+pub enum SquashMode {
+    /// Only the title (pull request title for GitHub squash merges)
+    Title,
+    /// Title and Conventional Commit bullets in the body
+    Body,
+}
+
+/// Origin of the base version
+#[derive(Debug, Clone, ValueEnum)]
+pub enum VersionSource {
+    /// Latest matching tag
+    Tag,
+    /// Release branch name, unless a tag of the same or a later release exists
+    Branch,
+}
+
+/// Letter case of the branch name in versions
+#[derive(Debug, Clone, ValueEnum)]
+pub enum BranchCase {
+    Lower,
+    Upper,
+    Preserve,
+}
+
+/// Handling of pre-release versions when selecting tags
+#[derive(Debug, Clone, ValueEnum)]
+pub enum PrereleaseFilter {
+    Include,
+    Exclude,
+    Only,
 }
 
 /// Output options
@@ -152,6 +413,11 @@ pub struct OutputOptions {
     pub format: Option<Format>,
     #[clap(short='o', long, help=format!("Template for resulting version [default: {}]", default::OUTPUT_TEMPLATE))]
     pub template: Option<String>,
+    #[clap(
+        long,
+        help = "Include GitVersion-compatible variables in JSON output, implies --format json [default: false]"
+    )]
+    pub verbose_json: bool,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -160,6 +426,21 @@ pub enum Format {
     Json,
 }
 
+/// Handling of shallow repositories, where tags and history may be missing
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ShallowPolicy {
+    Error,
+    Warn,
+    Ignore,
+}
+
+/// Set of fields included in JSON output
+#[derive(Debug, Clone, ValueEnum)]
+pub enum FieldSet {
+    Default,
+    All,
+}
+
 fn get_styles() -> Styles {
     Styles::styled()
         .header(RgbColor::from((246, 193, 119)).on_default() | Effects::BOLD)
@@ -0,0 +1,310 @@
+use git2::{Commit, DiffOptions, Error, Oid, Repository};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use semver::Version;
+
+use crate::{
+    cli::{BumpLevel, CommitConvention, SquashMode},
+    settings::{CommitSettings, ComponentSettings, Settings},
+    version::{discover_repository, find_latest_semver, open_repository, resolve_revision},
+};
+
+/// Commit trailers forcing the next version
+pub static RELEASE_AS_TRAILERS: [&str; 2] = ["Release-As", "Doxxer-Version"];
+
+pub static CONVENTIONAL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()]*)\))?(?P<breaking>!)?: (?P<description>.+)$",
+    )
+    .unwrap()
+});
+
+static BREAKING_FOOTER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^BREAKING[ -]CHANGE: ").unwrap());
+
+/// Commit message following the Conventional Commits specification
+#[derive(Debug, Clone)]
+pub struct ConventionalCommit {
+    pub kind: String,
+    pub scope: Option<String>,
+    pub breaking: bool,
+}
+
+/// Parses the commit message header and footers, returns `None` for non-conventional messages
+pub fn parse_conventional(message: &str) -> Option<ConventionalCommit> {
+    let (header, body) = message.split_once('\n').unwrap_or((message, ""));
+    let captures = CONVENTIONAL_REGEX.captures(header.trim_end())?;
+    Some(ConventionalCommit {
+        kind: captures["type"].to_lowercase(),
+        scope: captures
+            .name("scope")
+            .map(|scope| scope.as_str().to_string())
+            .filter(|scope| !scope.is_empty()),
+        breaking: captures.name("breaking").is_some() || BREAKING_FOOTER_REGEX.is_match(body),
+    })
+}
+
+static BULLET_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?m)^\s*[*-]\s+(.+)$").unwrap());
+
+static REVERT_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"This reverts commit ([0-9a-f]{7,40})").unwrap());
+
+/// Gitmojis implying a bump, as emoji and shortcode
+static GITMOJIS: [(&str, &str, BumpLevel); 3] = [
+    ("💥", ":boom:", BumpLevel::Major),
+    ("✨", ":sparkles:", BumpLevel::Minor),
+    ("🐛", ":bug:", BumpLevel::Patch),
+];
+
+fn conventional_bump_level(message: &str, settings: &CommitSettings) -> Option<BumpLevel> {
+    let conventional = parse_conventional(message)?;
+    if conventional.breaking {
+        Some(BumpLevel::Major)
+    } else if settings.minor_types.contains(&conventional.kind) {
+        Some(BumpLevel::Minor)
+    } else if settings.patch_types.contains(&conventional.kind) {
+        Some(BumpLevel::Patch)
+    } else {
+        None
+    }
+}
+
+fn gitmoji_bump_level(message: &str) -> Option<BumpLevel> {
+    let header = message.lines().next().unwrap_or_default();
+    GITMOJIS
+        .iter()
+        .filter(|(emoji, code, _)| header.contains(emoji) || header.contains(code))
+        .map(|(_, _, level)| level.clone())
+        .max()
+}
+
+fn regex_bump_level(message: &str, settings: &CommitSettings) -> Option<BumpLevel> {
+    let header = message.lines().next().unwrap_or_default();
+    settings
+        .patterns
+        .iter()
+        .filter(|(_, pattern)| pattern.is_match(header))
+        .map(|(level, _)| level.clone())
+        .max()
+}
+
+fn message_bump_level(message: &str, settings: &CommitSettings) -> Option<BumpLevel> {
+    match settings.convention {
+        CommitConvention::Conventional => conventional_bump_level(message, settings),
+        CommitConvention::Gitmoji => gitmoji_bump_level(message),
+        CommitConvention::Regex => regex_bump_level(message, settings),
+    }
+}
+
+/// Bump level implied by the commit, `None` for commits not requiring a release. Squash merges
+/// also contribute their Conventional Commit bullets when configured
+pub fn bump_level(commit: &Commit, settings: &CommitSettings) -> Option<BumpLevel> {
+    let message = commit.message().unwrap_or_default();
+    let level = message_bump_level(message, settings);
+    match settings.squash {
+        SquashMode::Title => level,
+        SquashMode::Body => {
+            let body = message
+                .split_once('\n')
+                .map(|(_, body)| body)
+                .unwrap_or_default();
+            BULLET_REGEX
+                .captures_iter(body)
+                .map(|bullet| message_bump_level(&bullet[1], settings))
+                .fold(level, Ord::max)
+        }
+    }
+}
+
+/// Commit reverted by given commit according to the message Git generates for reverts
+fn reverted_commit<'c, 'r>(commit: &Commit, commits: &'c [Commit<'r>]) -> Option<&'c Commit<'r>> {
+    let reverted = REVERT_REGEX.captures(commit.message().unwrap_or_default())?;
+    commits
+        .iter()
+        .find(|candidate| candidate.id().to_string().starts_with(&reverted[1]))
+}
+
+/// Highest bump level of all commits. A revert cancels the reverted commit if both are part of
+/// the commits
+pub fn infer_bump(commits: &[Commit], settings: &CommitSettings) -> Option<BumpLevel> {
+    let mut cancelled: Vec<Oid> = Vec::new();
+    let mut reverts: Vec<(Oid, Oid)> = Vec::new();
+    // Oldest first so that reverting a revert restores the originally reverted commit
+    for commit in commits.iter().rev() {
+        let reverted = match reverted_commit(commit, commits) {
+            Some(reverted) => reverted.id(),
+            None => continue,
+        };
+        match reverts.iter().find(|(revert, _)| *revert == reverted) {
+            Some((_, original)) => cancelled.retain(|oid| oid != original),
+            None => cancelled.push(reverted),
+        }
+        reverts.push((commit.id(), reverted));
+        cancelled.push(commit.id());
+    }
+    commits
+        .iter()
+        .filter(|commit| !cancelled.contains(&commit.id()))
+        .filter_map(|commit| bump_level(commit, settings))
+        .max()
+}
+
+fn resolve(repo: &Repository, rev: Option<&str>) -> Option<Oid> {
+    match resolve_revision(repo, rev) {
+        Ok(oid) => oid,
+        Err(e) => {
+            eprintln!("Issue resolving revision: {}!", e.message());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Opens the repository and resolves the bounds of the commit range. The range starts at the
+/// latest matching tag and ends at the target revision unless given explicitly
+pub fn release_range(
+    settings: &Settings,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> (Repository, Option<Oid>, Option<Oid>) {
+    let repo = open_repository(discover_repository(&settings.directory));
+    let target = resolve(&repo, to.or(settings.rev.as_deref()));
+    let from = match from {
+        Some(from) => resolve(&repo, Some(from)),
+        None => match find_latest_semver(&repo, &settings.filter, target) {
+            Ok(latest) => latest.map(|latest| latest.tag.commit),
+            Err(e) => {
+                eprintln!("Issue selecting latest tag: {}!", e.message());
+                std::process::exit(1);
+            }
+        },
+    };
+    (repo, from, target)
+}
+
+/// Checks whether the commit is scoped to the component or touches any of its paths
+fn belongs_to_component(repo: &Repository, commit: &Commit, component: &ComponentSettings) -> bool {
+    let scoped = parse_conventional(commit.message().unwrap_or_default())
+        .and_then(|conventional| conventional.scope)
+        .is_some_and(|scope| component.scopes.contains(&scope));
+    if scoped || component.paths.is_empty() {
+        return scoped;
+    }
+
+    let mut options = DiffOptions::new();
+    for path in &component.paths {
+        options.pathspec(path);
+    }
+    let parent_tree = commit.parent(0).and_then(|parent| parent.tree()).ok();
+    let tree = commit.tree().ok();
+    repo.diff_tree_to_tree(parent_tree.as_ref(), tree.as_ref(), Some(&mut options))
+        .is_ok_and(|diff| diff.deltas().len() > 0)
+}
+
+/// Commits between the latest tag and the target
+#[derive(Default)]
+pub struct CommitRange<'r> {
+    /// Commits that were not excluded and belong to the component, if any, newest first
+    pub commits: Vec<Commit<'r>>,
+    /// Number of commits skipped by the exclusion patterns
+    pub excluded: usize,
+}
+
+/// Commits reachable from the target (or HEAD) but not from the tag commit, filtered according
+/// to the commit settings
+pub fn commits_since<'r>(
+    repo: &'r Repository,
+    settings: &CommitSettings,
+    tag_commit: Option<Oid>,
+    target: Option<Oid>,
+) -> Result<CommitRange<'r>, Error> {
+    let mut revwalk = repo.revwalk()?;
+    if settings.first_parent {
+        revwalk.simplify_first_parent()?;
+    }
+    match target {
+        Some(oid) => revwalk.push(oid)?,
+        None => revwalk.push_head()?,
+    }
+    if let Some(oid) = tag_commit {
+        revwalk.hide(oid)?;
+    }
+
+    let mut range = CommitRange::default();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        // Commits of other components are not part of the range rather than excluded
+        if let Some(component) = &settings.component {
+            if !belongs_to_component(repo, &commit, component) {
+                continue;
+            }
+        }
+        if settings.is_excluded(&commit) {
+            range.excluded += 1;
+        } else {
+            range.commits.push(commit);
+        }
+    }
+    Ok(range)
+}
+
+/// Version requested by a commit through one of the release trailers
+#[derive(Debug, Clone)]
+pub struct ReleaseRequest {
+    pub commit: Oid,
+    pub version: Version,
+}
+
+fn release_request(commit: &Commit) -> Option<ReleaseRequest> {
+    let message = commit.message()?;
+    let trailers = git2::message_trailers_strs(message).ok()?;
+    let value = trailers.iter().find_map(|(key, value)| {
+        RELEASE_AS_TRAILERS
+            .iter()
+            .any(|trailer| trailer.eq_ignore_ascii_case(key))
+            .then_some(value)
+    })?;
+    let value = value.trim();
+    let version = Version::parse(value.strip_prefix('v').unwrap_or(value)).ok()?;
+    Some(ReleaseRequest {
+        commit: commit.id(),
+        version,
+    })
+}
+
+/// Finds the version requested by release trailers. All requests must agree on the version,
+/// otherwise they are returned as error
+pub fn release_as(commits: &[Commit]) -> Result<Option<Version>, Vec<ReleaseRequest>> {
+    let requests: Vec<ReleaseRequest> = commits.iter().filter_map(release_request).collect();
+    match requests.first() {
+        Some(first) if requests.iter().all(|r| r.version == first.version) => {
+            Ok(Some(first.version.clone()))
+        }
+        Some(_) => Err(requests),
+        None => Ok(None),
+    }
+}
+
+/// Version requested by `Release-As` trailers and bump level required by the commits. Commits
+/// requesting different versions are reported as an error
+pub fn required_release(
+    commits: &[Commit],
+    settings: &CommitSettings,
+) -> (Option<Version>, Option<BumpLevel>) {
+    let requested = match release_as(commits) {
+        Ok(version) => version,
+        Err(requests) => {
+            match &settings.component {
+                Some(component) => eprintln!(
+                    "Commits of component \"{}\" request conflicting release versions:",
+                    component.name
+                ),
+                None => eprintln!("Commits request conflicting release versions:"),
+            }
+            for request in requests {
+                eprintln!("  {} {}", &request.commit.to_string()[..7], request.version);
+            }
+            std::process::exit(1);
+        }
+    };
+    (requested, infer_bump(commits, settings))
+}
@@ -1,70 +1,163 @@
 use config::Config;
+use regex::Regex;
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::default;
 
+/// Compares directories by their canonical paths, falling back to the given paths
+pub fn is_same_directory(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
 /// Thin wrapper around config::Config to implement related utility methods
 #[derive(Debug)]
 pub struct Configuration {
     config: Config,
+    /// Index of the `[[branches]]` rule matching the current branch
+    branch_rule: Option<usize>,
+    /// Branch the rules were selected for
+    branch: Option<String>,
 }
 impl Configuration {
-    /// Loads config from directory/file. Uses default config locations if none is provided
-    pub fn load(config_path: Option<&PathBuf>) -> Self {
+    /// Loads config from directory/file. Uses default config locations between the repository root
+    /// and the working directory if none is provided
+    pub fn load(config_path: Option<&PathBuf>, root: Option<&Path>) -> Self {
         let hidden_config_file_name = format!(".{}", default::CONFIG_FILE_NAME);
 
-        let mut config = Config::builder()
-            .add_source(config::File::with_name(&hidden_config_file_name).required(false))
-            .add_source(config::File::with_name(default::CONFIG_FILE_NAME).required(false));
+        let mut config = Config::builder();
+        for dir in Self::search_directories(root) {
+            config = config
+                .add_source(
+                    config::File::with_name(dir.join(&hidden_config_file_name).to_str().unwrap())
+                        .required(false),
+                )
+                .add_source(
+                    config::File::with_name(dir.join(default::CONFIG_FILE_NAME).to_str().unwrap())
+                        .required(false),
+                );
+        }
 
-        match config_path {
-            Some(path) => {
-                if path.is_file() {
-                    config = config.add_source(config::File::with_name(
-                        std::path::absolute(path).unwrap().to_str().unwrap(),
-                    ));
-                } else {
-                    config = config
-                        .add_source(
-                            config::File::with_name(
-                                path.join(&hidden_config_file_name)
-                                    .as_os_str()
-                                    .to_str()
-                                    .unwrap(),
-                            )
-                            .required(false),
+        if let Some(path) = config_path {
+            if path.is_file() {
+                config = config.add_source(config::File::with_name(
+                    std::path::absolute(path).unwrap().to_str().unwrap(),
+                ));
+            } else {
+                config = config
+                    .add_source(
+                        config::File::with_name(
+                            path.join(&hidden_config_file_name)
+                                .as_os_str()
+                                .to_str()
+                                .unwrap(),
                         )
-                        .add_source(
-                            config::File::with_name(
-                                path.join(default::CONFIG_FILE_NAME)
-                                    .as_os_str()
-                                    .to_str()
-                                    .unwrap(),
-                            )
-                            .required(false),
-                        );
-                }
+                        .required(false),
+                    )
+                    .add_source(
+                        config::File::with_name(
+                            path.join(default::CONFIG_FILE_NAME)
+                                .as_os_str()
+                                .to_str()
+                                .unwrap(),
+                        )
+                        .required(false),
+                    );
             }
-            None => {}
         }
 
         config = config.add_source(
             config::Environment::with_prefix("DOXXER")
                 .separator("__")
                 .try_parsing(true)
-                .list_separator(","),
+                .list_separator(",")
+                .with_list_parse_key("filter.exclude")
+                .with_list_parse_key("branch.strip_prefixes")
+                .with_list_parse_key("commits.exclude")
+                .with_list_parse_key("commits.types")
+                .with_list_parse_key("commits.scopes")
+                .with_list_parse_key("commits.minor_types")
+                .with_list_parse_key("commits.patch_types")
+                .with_list_parse_key("commits.patterns.major")
+                .with_list_parse_key("commits.patterns.minor")
+                .with_list_parse_key("commits.patterns.patch"),
         );
         Self {
             config: config.build().expect("Failed to load config"),
+            branch_rule: None,
+            branch: None,
         }
     }
 
+    /// Directories searched for default config files, ordered from the repository root down to
+    /// the current working directory so that deeper config files take precedence
+    fn search_directories(root: Option<&Path>) -> Vec<PathBuf> {
+        let cwd = match std::env::current_dir() {
+            Ok(cwd) => cwd,
+            Err(_) => return vec![PathBuf::from(".")],
+        };
+
+        // Fall back to the working directory alone when it is outside of the work tree
+        let depth =
+            root.and_then(|root| cwd.ancestors().position(|dir| is_same_directory(dir, root)));
+        let mut directories: Vec<PathBuf> = cwd
+            .ancestors()
+            .take(depth.unwrap_or(0) + 1)
+            .map(Path::to_path_buf)
+            .collect();
+        directories.reverse();
+        directories
+    }
+
+    /// Activates the first `[[branches]]` rule whose pattern matches the branch name. Values of
+    /// the active rule take precedence over all other configuration
+    pub fn select_branch(&mut self, branch: &str) {
+        self.branch = Some(branch.to_string());
+        let rules = self
+            .config
+            .get::<Vec<config::Value>>("branches")
+            .unwrap_or_default();
+        for i in 0..rules.len() {
+            let pattern = match self
+                .config
+                .get::<String>(&format!("branches[{}].pattern", i))
+            {
+                Ok(pattern) => pattern,
+                Err(_) => {
+                    eprintln!("Branch rule #{} is missing required key \"pattern\"", i + 1);
+                    std::process::exit(1);
+                }
+            };
+            let regex = Regex::new(&pattern).unwrap_or_else(|e| {
+                eprintln!("Invalid branch pattern \"{}\": {}", pattern, e);
+                std::process::exit(1);
+            });
+            if regex.is_match(branch) {
+                self.branch_rule = Some(i);
+                return;
+            }
+        }
+    }
+
+    /// Branch the configuration rules were selected for
+    pub fn branch(&self) -> Option<&str> {
+        self.branch.as_deref()
+    }
+
     /// Get value for configuration item dpending on subcommand. Returns error if not found in configuration
     pub fn get<'de, T>(&self, subcommand: &str, key: &str) -> Result<T, config::ConfigError>
     where
         T: Deserialize<'de>,
     {
+        if let Some(i) = self.branch_rule {
+            if let Ok(value) = self.config.get::<T>(&format!("branches[{}].{}", i, key)) {
+                return Ok(value);
+            }
+        }
+
         let parts: Vec<&str> = subcommand.split('.').collect();
 
         // Try from the most specific to the least specific subcommand prefix
@@ -0,0 +1,105 @@
+use std::{env, fs, path::Path};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use semver::Version;
+
+use crate::{
+    settings::Settings,
+    version::{parse_tag_version, VersionContext, SEMVER_REGEX},
+};
+
+/// Environment variable that overrides the resulting version entirely
+pub static PRETEND_VERSION_VARIABLE: &str = "DOXXER_PRETEND_VERSION";
+
+/// File populated by `git archive` through the `export-subst` attribute
+pub static ARCHIVAL_FILE_NAME: &str = ".git_archival.txt";
+
+static DESCRIBE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?P<tag>.+)-(?P<distance>\d+)-g(?P<hash>[0-9a-f]+)$").unwrap());
+
+static MANIFEST_VERSION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?m)^\s*"?version"?\s*[:=]\s*["']?([^"'\s,]+)"#).unwrap());
+
+/// Version obtained without reading the Git history
+#[derive(Debug)]
+pub struct FallbackVersion {
+    pub version: Version,
+    pub context: VersionContext,
+}
+
+/// Reads the pretend version from the environment. Exits if it is not valid SemVer
+pub fn pretend_version() -> Option<Version> {
+    let value = env::var(PRETEND_VERSION_VARIABLE).ok()?;
+    match Version::parse(value.trim()) {
+        Ok(version) => Some(version),
+        Err(e) => {
+            eprintln!(
+                "Invalid version \"{}\" in {}: {}!",
+                value, PRETEND_VERSION_VARIABLE, e
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Determines the version from `.git_archival.txt` or the configured manifest file, in that order
+pub fn fallback_version(settings: &Settings) -> Option<FallbackVersion> {
+    from_archival_file(&settings.directory.join(ARCHIVAL_FILE_NAME), settings).or_else(|| {
+        let manifest = settings.fallback.manifest.as_ref()?;
+        from_manifest(&settings.directory.join(manifest))
+    })
+}
+
+fn from_archival_file(path: &Path, settings: &Settings) -> Option<FallbackVersion> {
+    let content = fs::read_to_string(path).ok()?;
+
+    let mut describe_name: Option<&str> = None;
+    let mut node: Option<&str> = None;
+    for line in content.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            let value = value.trim();
+            // Placeholders are left untouched when the file was not exported by `git archive`
+            if value.contains("$Format") {
+                continue;
+            }
+            match key.trim() {
+                "describe-name" => describe_name = Some(value),
+                "node" => node = Some(value),
+                _ => {}
+            }
+        }
+    }
+
+    let describe_name = describe_name?;
+    let (tag, distance) = match DESCRIBE_REGEX.captures(describe_name) {
+        Some(captures) => (
+            captures.name("tag").unwrap().as_str(),
+            captures["distance"].parse::<usize>().unwrap_or_default(),
+        ),
+        None => (describe_name, 0),
+    };
+    let version = parse_tag_version(&settings.filter, tag)?;
+
+    Some(FallbackVersion {
+        version,
+        context: VersionContext {
+            sha: node.unwrap_or_default().to_string(),
+            distance,
+            ..Default::default()
+        },
+    })
+}
+
+fn from_manifest(path: &Path) -> Option<FallbackVersion> {
+    let content = fs::read_to_string(path).ok()?;
+    let version = match MANIFEST_VERSION_REGEX.captures(&content) {
+        Some(captures) => Version::parse(&captures[1]).ok()?,
+        // Plain version files only contain the version itself
+        None => Version::parse(SEMVER_REGEX.find(&content)?.as_str()).ok()?,
+    };
+    Some(FallbackVersion {
+        version,
+        context: VersionContext::default(),
+    })
+}
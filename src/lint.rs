@@ -0,0 +1,166 @@
+use std::{fs, io::Read, path::Path};
+
+use crate::{
+    commits::CONVENTIONAL_REGEX,
+    settings::{CommitSettings, Settings},
+    version::{discover_repository, open_repository},
+};
+
+/// Line below which `git commit --verbose` appends the diff
+static SCISSORS_LINE: &str = "# ------------------------ >8 ------------------------";
+
+/// Messages generated by Git itself are accepted as is
+static GENERATED_PREFIXES: [&str; 4] = ["Merge ", "Revert \"", "fixup! ", "squash! "];
+
+static COMMIT_MSG_HOOK: &str = "#!/bin/sh\n# Installed by doxxer\nexec doxxer lint-commit \"$1\"\n";
+
+/// Removes comment lines and the diff of verbose commits, as Git does with default cleanup
+fn cleanup_message(message: &str) -> String {
+    message
+        .lines()
+        .take_while(|line| *line != SCISSORS_LINE)
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Validates commit message against the Conventional Commits grammar and configured types/scopes
+pub fn lint_message(message: &str, settings: &CommitSettings) -> Result<(), String> {
+    let message = cleanup_message(message);
+    let mut lines = message.lines();
+    let header = match lines.next() {
+        Some(header) => header,
+        None => return Err("commit message is empty".to_string()),
+    };
+    if GENERATED_PREFIXES
+        .iter()
+        .any(|prefix| header.starts_with(prefix))
+    {
+        return Ok(());
+    }
+
+    let captures = CONVENTIONAL_REGEX.captures(header).ok_or_else(|| {
+        format!(
+            "header \"{}\" does not follow Conventional Commits (type(scope)!: description)",
+            header
+        )
+    })?;
+    let kind = captures["type"].to_lowercase();
+    if !settings.types.contains(&kind) {
+        return Err(format!(
+            "type \"{}\" is not allowed (allowed: {})",
+            kind,
+            settings.types.join(", ")
+        ));
+    }
+    if let Some(scope) = captures.name("scope") {
+        if !settings.scopes.is_empty() && !settings.scopes.iter().any(|s| s == scope.as_str()) {
+            return Err(format!(
+                "scope \"{}\" is not allowed (allowed: {})",
+                scope.as_str(),
+                settings.scopes.join(", ")
+            ));
+        }
+    }
+    if lines.next().is_some_and(|line| !line.is_empty()) {
+        return Err("header must be followed by a blank line".to_string());
+    }
+    Ok(())
+}
+
+/// Lints the commit message in given file, `-` reads from standard input
+pub fn lint_commit(settings: &Settings, file: &Path) {
+    let message = if file == Path::new("-") {
+        let mut message = String::new();
+        std::io::stdin()
+            .read_to_string(&mut message)
+            .map(|_| message)
+    } else {
+        fs::read_to_string(file)
+    };
+    let message = message.unwrap_or_else(|e| {
+        eprintln!("Issue reading commit message: {}!", e);
+        std::process::exit(1);
+    });
+    if let Err(e) = lint_message(&message, &settings.commits) {
+        eprintln!("Invalid commit message: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Lints messages of all commits in the revision range (e.g., `main..HEAD`)
+pub fn lint_range(settings: &Settings, range: &str) {
+    let repo = open_repository(discover_repository(&settings.directory));
+    let walk = || -> Result<Vec<git2::Oid>, git2::Error> {
+        let mut revwalk = repo.revwalk()?;
+        if range.contains("..") {
+            revwalk.push_range(range)?;
+        } else {
+            revwalk.push(repo.revparse_single(range)?.peel_to_commit()?.id())?;
+        }
+        revwalk.collect()
+    };
+    let oids = walk().unwrap_or_else(|e| {
+        eprintln!("Issue resolving revision range: {}!", e.message());
+        std::process::exit(1);
+    });
+
+    let mut invalid = 0;
+    for oid in oids {
+        let commit = match repo.find_commit(oid) {
+            Ok(commit) => commit,
+            Err(_) => continue,
+        };
+        if settings.commits.is_excluded(&commit) {
+            continue;
+        }
+        if let Err(e) = lint_message(commit.message().unwrap_or_default(), &settings.commits) {
+            eprintln!(
+                "{} {}: {}",
+                &oid.to_string()[..7],
+                commit.summary().unwrap_or_default(),
+                e
+            );
+            invalid += 1;
+        }
+    }
+    if invalid > 0 {
+        eprintln!("Found {} invalid commit message(s)!", invalid);
+        std::process::exit(1);
+    }
+}
+
+/// Writes a `commit-msg` hook linting every new commit message
+pub fn install_hooks(settings: &Settings, force: bool) {
+    let repo = open_repository(discover_repository(&settings.directory));
+    // Hooks are shared by linked worktrees unless `core.hooksPath` relocates them, relative paths
+    // are resolved against the work tree like git does
+    let hooks = match repo
+        .config()
+        .and_then(|config| config.get_path("core.hooksPath"))
+    {
+        Ok(path) => repo.workdir().unwrap_or_else(|| repo.path()).join(path),
+        Err(_) => repo.commondir().join("hooks"),
+    };
+    let hook = hooks.join("commit-msg");
+    if hook.exists() && !force {
+        eprintln!(
+            "Hook {} already exists, use --force to overwrite it!",
+            hook.display()
+        );
+        std::process::exit(1);
+    }
+    let written = fs::create_dir_all(&hooks).and_then(|_| fs::write(&hook, COMMIT_MSG_HOOK));
+    if let Err(e) = written {
+        eprintln!("Issue writing hook {}: {}!", hook.display(), e);
+        std::process::exit(1);
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(&hook, fs::Permissions::from_mode(0o755));
+    }
+    println!("Installed {}", hook.display());
+}
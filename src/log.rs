@@ -0,0 +1,106 @@
+use git2::Commit;
+use serde_json::{json, Value};
+
+use crate::{
+    cli::Format,
+    commits::{commits_since, parse_conventional, release_range},
+    settings::Settings,
+};
+
+/// Single row of the commit log
+struct LogEntry {
+    hash: String,
+    subject: String,
+    author: String,
+    kind: Option<String>,
+    scope: Option<String>,
+    breaking: bool,
+}
+impl LogEntry {
+    fn new(commit: &Commit) -> Self {
+        let conventional = parse_conventional(commit.message().unwrap_or_default());
+        Self {
+            hash: commit.id().to_string(),
+            subject: commit.summary().unwrap_or_default().to_string(),
+            author: commit.author().name().unwrap_or_default().to_string(),
+            kind: conventional.as_ref().map(|c| c.kind.clone()),
+            scope: conventional.as_ref().and_then(|c| c.scope.clone()),
+            breaking: conventional.is_some_and(|c| c.breaking),
+        }
+    }
+
+    fn short_hash(&self) -> &str {
+        &self.hash[..7]
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "hash": self.hash,
+            "short_hash": self.short_hash(),
+            "subject": self.subject,
+            "author": self.author,
+            "type": self.kind,
+            "scope": self.scope,
+            "breaking": self.breaking,
+        })
+    }
+}
+
+/// Prints the commits between `from` (latest matching tag by default) and `to` (target revision
+/// by default) with their Conventional Commit classification
+pub fn print_log(settings: &Settings, from: Option<&str>, to: Option<&str>) {
+    let (repo, from, target) = release_range(settings, from, to);
+    let range = match commits_since(&repo, &settings.commits, from, target) {
+        Ok(range) => range,
+        Err(e) => {
+            eprintln!("Issue reading commits: {}!", e.message());
+            std::process::exit(1);
+        }
+    };
+    let entries: Vec<LogEntry> = range.commits.iter().map(LogEntry::new).collect();
+
+    match settings.output.format {
+        Format::Plain => {
+            let rows: Vec<[String; 6]> = entries
+                .iter()
+                .map(|entry| {
+                    [
+                        entry.short_hash().to_string(),
+                        entry.kind.clone().unwrap_or_default(),
+                        entry.scope.clone().unwrap_or_default(),
+                        if entry.breaking { "yes" } else { "" }.to_string(),
+                        entry.author.clone(),
+                        entry.subject.clone(),
+                    ]
+                })
+                .collect();
+            print_table(
+                ["COMMIT", "TYPE", "SCOPE", "BREAKING", "AUTHOR", "SUBJECT"],
+                &rows,
+            );
+        }
+        Format::Json => {
+            let entries: Vec<Value> = entries.iter().map(LogEntry::to_json).collect();
+            println!("{}", serde_json::to_string_pretty(&entries).unwrap());
+        }
+    }
+}
+
+/// Prints rows as columns aligned to their widest value
+pub fn print_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) {
+    let header = header.map(String::from);
+    let mut widths = header.clone().map(|column| column.chars().count());
+    for row in rows {
+        for (width, column) in widths.iter_mut().zip(row) {
+            *width = (*width).max(column.chars().count());
+        }
+    }
+    for row in std::iter::once(&header).chain(rows) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(column, width)| format!("{:<width$}", column, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+}
@@ -1,17 +1,35 @@
+mod cargo;
+mod check;
+mod ci;
 mod cli;
+mod commits;
 mod config;
+mod fallback;
+mod lint;
+mod log;
+mod plan;
 mod settings;
+mod signature;
 mod template;
 mod version;
 
+use std::path::PathBuf;
+
 use clap::Parser;
+use git2::Repository;
 
-use cli::{BuildMetadataOptions, Cli, Commands, PrereleaseArgs, PrereleaseOptions, Strategy};
+use cargo::workspace_members;
+use cli::{
+    BuildMetadataOptions, Cli, Commands, HooksAction, PrereleaseArgs, PrereleaseOptions,
+    ShallowPolicy, Strategy,
+};
 use config::Configuration;
+use fallback::{fallback_version, pretend_version};
 use settings::Settings;
-use version::{current_version, format_version, next_version};
-
-use git2::Repository;
+use version::{
+    compute_next, discover_repository, format_version, get_current_branch_name, latest_version,
+    next_version, open_repository, VersionContext,
+};
 
 pub mod default {
     pub static CONFIG_FILE_NAME: &str = "doxxer";
@@ -22,6 +40,20 @@ pub mod default {
 
     pub static OUTPUT_TEMPLATE: &str = "{version}";
 
+    pub static COMPONENT_TAG_TEMPLATE: &str = "{component}-v{version}";
+
+    /// Exit status of `check` when no release is needed, distinct from errors (1) and usage (2)
+    pub static NO_RELEASE_EXIT_CODE: i32 = 3;
+
+    pub static COMMIT_TYPES: [&str; 11] = [
+        "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style",
+        "test",
+    ];
+    pub static MINOR_TYPES: [&str; 1] = ["feat"];
+    pub static PATCH_TYPES: [&str; 2] = ["fix", "perf"];
+
+    pub static BRANCH_VERSION_PATTERN: &str = r"^release/(?P<major>\d+)\.(?P<minor>\d+)";
+
     pub static INCREMENT: u64 = 1;
 
     pub static PRERELEASE_IDENTIFIER: &str = "build";
@@ -35,39 +67,146 @@ pub mod default {
     pub static DEV_BUILD_METADATA_TEMPLATE: &str = "{hash}";
 }
 
-fn main() {
-    let cli = Cli::parse();
+/// Determines the branch used for selecting `[[branches]]` configuration rules
+fn current_branch(cli: &Cli, repo: Option<&Repository>) -> Option<String> {
+    if let Some(branch) = &cli.branch {
+        return Some(branch.clone());
+    }
+    let branch = repo.and_then(|repo| {
+        get_current_branch_name(repo, cli.rev.as_deref())
+            .ok()
+            .flatten()
+    });
+    // CI checkouts are often detached, so the branch is recovered from the CI environment
+    branch.or_else(|| ci::detect().and_then(|ci| ci.branch))
+}
 
-    let config = match &cli.config {
-        Some(config_path) => Configuration::load(Some(config_path)),
+/// Warns about or rejects shallow clones, which may lack tags and history
+fn apply_shallow_policy(repo: &Repository, policy: &ShallowPolicy) {
+    if !repo.is_shallow() {
+        return;
+    }
+    match policy {
+        ShallowPolicy::Error => {
+            eprintln!(
+                "Repository is a shallow clone, tags and history may be missing! \
+                 Fetch full history (e.g. `git fetch --unshallow --tags`)."
+            );
+            std::process::exit(1);
+        }
+        ShallowPolicy::Warn => {
+            eprintln!("Warning: repository is a shallow clone, tags and history may be missing!");
+        }
+        ShallowPolicy::Ignore => {}
+    }
+}
+
+fn main() {
+    let mut cli = Cli::parse();
+
+    // Default config files are searched up to the root of the repository in the working directory
+    let root: Option<PathBuf> = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| discover_repository(&cwd).ok())
+        .map(|repo| repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf());
+    let mut config = match &cli.config {
+        Some(config_path) => Configuration::load(Some(config_path), root.as_deref()),
         None => match &cli.directory {
-            Some(dir) => Configuration::load(Some(dir)),
-            None => Configuration::load(None),
+            Some(dir) => Configuration::load(Some(dir), root.as_deref()),
+            None => Configuration::load(None, root.as_deref()),
         },
     };
 
-    let settings = Settings::merge(&cli, &config);
+    let repo = discover_repository(&Settings::directory(&cli, &config));
+    let branch = current_branch(&cli, repo.as_ref().ok());
+    if let Some(branch) = &branch {
+        config.select_branch(branch);
+    }
+
+    // Configured default strategy is used when none is given
+    if let Commands::Next { strategy, .. } = &mut cli.cmd {
+        if strategy.is_none() {
+            if let Ok(name) = config.get::<String>("next", "strategy") {
+                *strategy = match Strategy::from_name(&name) {
+                    Some(s) => Some(s),
+                    None => {
+                        eprintln!("Unknown bumping strategy \"{}\"", name);
+                        std::process::exit(1);
+                    }
+                };
+            }
+        }
+    }
+
+    // Components without configuration may be members of the Cargo workspace
+    let members = match &repo {
+        Ok(repo) => repo.workdir().map(workspace_members).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    let settings = Settings::merge(&cli, &config, &members);
     settings.validate();
 
-    let repo = match Repository::open(&settings.directory) {
-        Ok(repo) => repo,
-        Err(e) => {
-            eprintln!("Issue opening repository: {}!", e.message());
-            std::process::exit(1);
+    // Pretend version is reported as is, regardless of the repository and bumping strategy
+    let pretend = pretend_version()
+        .filter(|_| matches!(cli.cmd, Commands::Current { .. } | Commands::Next { .. }));
+    // Everything but commit message linting depends on the tags and history of the repository
+    let reads_history = !matches!(
+        cli.cmd,
+        Commands::LintCommit { .. } | Commands::Hooks { .. }
+    );
+    if let (Ok(repo), None, true) = (&repo, &pretend, reads_history) {
+        apply_shallow_policy(repo, &settings.shallow);
+    }
+
+    let field = match &cli.cmd {
+        Commands::Current { field } | Commands::Next { field, .. } => field,
+        Commands::Log { from, to } => {
+            return log::print_log(&settings, from.as_deref(), to.as_deref());
+        }
+        Commands::Check { bump } => return check::check(&settings, *bump),
+        Commands::LintCommit { file } => return lint::lint_commit(&settings, file),
+        Commands::LintRange { range } => return lint::lint_range(&settings, range),
+        Commands::Hooks {
+            action: HooksAction::Install { force },
+        } => return lint::install_hooks(&settings, *force),
+        // Release plans merge settings for every component themselves
+        Commands::Plan => {
+            return plan::print_plan(&cli, &config, &open_repository(repo), &members);
+        }
+        Commands::Workspace { write } => {
+            return plan::print_workspace(&cli, &config, &open_repository(repo), &members, *write);
         }
     };
 
+    if let Some(version) = pretend {
+        return format_version(
+            field,
+            &version,
+            &VersionContext::default(),
+            &settings.output,
+        );
+    }
+
+    let (latest, context) = match repo {
+        Ok(repo) => match &cli.cmd {
+            Commands::Next { .. } => compute_next(&settings, &repo),
+            // Only `next` is based on the branch version, `current` reports tagged versions
+            _ => {
+                let (latest, context, _) = latest_version(&settings, &repo);
+                (latest, context)
+            }
+        },
+        Err(e) => match fallback_version(&settings) {
+            Some(fallback) => (fallback.version, fallback.context),
+            None => {
+                eprintln!("Issue opening repository: {}!", e.message());
+                std::process::exit(1);
+            }
+        },
+    };
+
     match &cli.cmd {
-        Commands::Current { field } => {
-            let version = current_version(&repo, &settings.filter.tag);
-            format_version(
-                field,
-                &version,
-                &settings.output.format,
-                &settings.output.template,
-            )
-        }
-        Commands::Next { field, strategy } => {
+        Commands::Next { strategy, .. } => {
             let strategy = match strategy {
                 Some(s) => s,
                 None => &Strategy::Dev(PrereleaseArgs {
@@ -82,13 +221,9 @@ fn main() {
                     },
                 }),
             };
-            let version = next_version(&repo, strategy, &settings);
-            format_version(
-                field,
-                &version,
-                &settings.output.format,
-                &settings.output.template,
-            )
+            let version = next_version(latest, strategy, &settings, &context);
+            format_version(field, &version, &context, &settings.output)
         }
+        _ => format_version(field, &latest, &context, &settings.output),
     }
 }
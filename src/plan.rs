@@ -0,0 +1,329 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum as _;
+use git2::Repository;
+use semver::Version;
+use serde_json::{json, Value};
+
+use crate::{
+    cargo::{
+        inherits_version, package_name, path_dependencies, set_package_version,
+        set_workspace_version, update_requirements, WorkspaceMember, MANIFEST_FILE_NAME,
+    },
+    cli::{BumpLevel, Cli, Format, Strategy},
+    config::{is_same_directory, Configuration},
+    log::print_table,
+    settings::{ComponentSettings, Settings},
+    version::{compute_next, next_version, VersionContext},
+};
+
+/// Why a component is released
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Reason {
+    Commits,
+    Dependency,
+}
+impl Reason {
+    fn name(&self) -> &'static str {
+        match self {
+            Reason::Commits => "commits",
+            Reason::Dependency => "dependency",
+        }
+    }
+}
+
+/// Planned release of a single component
+struct PlanEntry {
+    component: ComponentSettings,
+    settings: Settings,
+    context: VersionContext,
+    current: Version,
+    dependencies: Vec<String>,
+    /// Directories of crates the component's manifest depends on
+    crates: Vec<PathBuf>,
+    reason: Option<Reason>,
+    next: Option<Version>,
+}
+impl PlanEntry {
+    fn next_tag(&self) -> Option<String> {
+        self.next.as_ref().map(|next| {
+            self.component
+                .tag_template
+                .replace("{version}", &next.to_string())
+        })
+    }
+
+    fn bump_name(&self) -> Option<String> {
+        self.next
+            .as_ref()
+            .and(self.context.bump.as_ref())
+            .map(|bump| bump.to_possible_value().unwrap().get_name().to_string())
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "name": self.component.name,
+            "tag": self.context.tag,
+            "current": self.current.to_string(),
+            "next": self.next.as_ref().map(Version::to_string),
+            "next_tag": self.next_tag(),
+            "bump": self.bump_name(),
+            "reason": self.reason.map(|reason| reason.name()),
+            "dependencies": self.dependencies,
+            "manifest": self.component.manifest,
+        })
+    }
+}
+
+/// Names of the configured components and Cargo workspace members
+fn component_names(config: &Configuration, members: &[WorkspaceMember]) -> Vec<String> {
+    let mut names: Vec<String> = config
+        .get::<BTreeMap<String, config::Value>>("plan", "components")
+        .unwrap_or_default()
+        .into_keys()
+        .collect();
+    names.extend(members.iter().map(|member| member.name.clone()));
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Work tree root of the repository
+fn repository_root(repo: &Repository) -> PathBuf {
+    repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf()
+}
+
+/// Adds components owning the crates of `path` dependencies to the dependencies and checks that
+/// all dependencies are known components
+fn resolve_dependencies(entries: &mut [PlanEntry]) {
+    let owners: Vec<(String, Vec<PathBuf>)> = entries
+        .iter()
+        .map(|entry| (entry.component.name.clone(), entry.component.paths.clone()))
+        .collect();
+    for entry in entries.iter_mut() {
+        for directory in &entry.crates {
+            let owner = owners
+                .iter()
+                .find(|(_, paths)| paths.iter().any(|path| is_same_directory(path, directory)));
+            if let Some((name, _)) = owner {
+                if *name != entry.component.name && !entry.dependencies.contains(name) {
+                    entry.dependencies.push(name.clone());
+                }
+            }
+        }
+        for dependency in &entry.dependencies {
+            if !owners.iter().any(|(name, _)| name == dependency) {
+                eprintln!(
+                    "Component \"{}\" depends on unknown component \"{}\"!",
+                    entry.component.name, dependency
+                );
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Determines the next version of every component. Components with releasable commits are bumped
+/// accordingly, components depending on released components get a patch release
+fn plan_releases(
+    cli: &Cli,
+    config: &Configuration,
+    repo: &Repository,
+    members: &[WorkspaceMember],
+    names: &[String],
+) -> Vec<PlanEntry> {
+    let root = repository_root(repo);
+    let mut entries: Vec<PlanEntry> = Vec::new();
+    for name in names {
+        let settings = Settings::merge_component(cli, config, members, Some(name));
+        settings.validate();
+        let mut component = settings.commits.component.clone().unwrap();
+        let (current, context) = compute_next(&settings, repo);
+
+        // Manifests are looked up in the first component path unless configured
+        if component.manifest.is_none() {
+            component.manifest = component
+                .paths
+                .first()
+                .map(|path| path.join(MANIFEST_FILE_NAME))
+                .filter(|manifest| root.join(manifest).is_file());
+        }
+        let crates = match &component.manifest {
+            Some(manifest) => path_dependencies(&root.join(manifest), &root),
+            None => Vec::new(),
+        };
+        // Component paths are relative to the repository root
+        component.paths = component.paths.iter().map(|path| root.join(path)).collect();
+
+        entries.push(PlanEntry {
+            current,
+            dependencies: component.dependencies.clone(),
+            crates,
+            reason: (context.bump.is_some() || context.release_as.is_some())
+                .then_some(Reason::Commits),
+            next: None,
+            component,
+            settings,
+            context,
+        });
+    }
+    resolve_dependencies(&mut entries);
+
+    // Dependents are released until no further component is affected
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for i in 0..entries.len() {
+            if entries[i].reason.is_some() {
+                continue;
+            }
+            let released = entries[i].dependencies.iter().any(|dependency| {
+                entries
+                    .iter()
+                    .any(|entry| &entry.component.name == dependency && entry.reason.is_some())
+            });
+            if released {
+                entries[i].context.bump = Some(BumpLevel::Patch);
+                entries[i].reason = Some(Reason::Dependency);
+                changed = true;
+            }
+        }
+    }
+
+    let strategy = Strategy::from_name("auto").unwrap();
+    for entry in entries.iter_mut().filter(|entry| entry.reason.is_some()) {
+        entry.next = Some(next_version(
+            entry.current.clone(),
+            &strategy,
+            &entry.settings,
+            &entry.context,
+        ));
+    }
+
+    entries
+}
+
+fn print_entries(entries: &[PlanEntry], key: &str, column: &str) {
+    match entries.first().map(|entry| &entry.settings.output.format) {
+        Some(Format::Json) => {
+            let components: Vec<Value> = entries.iter().map(PlanEntry::to_json).collect();
+            let plan = json!({ key: components });
+            println!("{}", serde_json::to_string_pretty(&plan).unwrap());
+        }
+        _ => {
+            let rows: Vec<[String; 5]> = entries
+                .iter()
+                .map(|entry| {
+                    [
+                        entry.component.name.clone(),
+                        entry.current.to_string(),
+                        entry
+                            .next
+                            .as_ref()
+                            .map(Version::to_string)
+                            .unwrap_or_default(),
+                        entry.bump_name().unwrap_or_default(),
+                        entry
+                            .reason
+                            .map(|reason| reason.name())
+                            .unwrap_or_default()
+                            .to_string(),
+                    ]
+                })
+                .collect();
+            print_table([column, "CURRENT", "NEXT", "BUMP", "REASON"], &rows);
+        }
+    }
+}
+
+/// Prints the release plan of all components
+pub fn print_plan(
+    cli: &Cli,
+    config: &Configuration,
+    repo: &Repository,
+    members: &[WorkspaceMember],
+) {
+    let names = component_names(config, members);
+    if names.is_empty() {
+        eprintln!("No components configured!");
+        std::process::exit(1);
+    }
+    let entries = plan_releases(cli, config, repo, members, &names);
+    print_entries(&entries, "components", "COMPONENT");
+}
+
+/// Prints the release plan of the Cargo workspace members. Optionally writes the next versions to
+/// the member manifests along with the requirements of internal dependencies
+pub fn print_workspace(
+    cli: &Cli,
+    config: &Configuration,
+    repo: &Repository,
+    members: &[WorkspaceMember],
+    write: bool,
+) {
+    let root = repository_root(repo);
+    if members.is_empty() {
+        eprintln!(
+            "No Cargo workspace members found in {}!",
+            root.join(MANIFEST_FILE_NAME).display()
+        );
+        std::process::exit(1);
+    }
+    let names: Vec<String> = members.iter().map(|member| member.name.clone()).collect();
+    let entries = plan_releases(cli, config, repo, members, &names);
+
+    if write {
+        write_versions(&root, members, &entries);
+    }
+    print_entries(&entries, "crates", "CRATE");
+}
+
+/// Writes the next versions to the member manifests and updates the requirements on released
+/// crates. Crates inheriting the workspace version must all be released with the same version
+fn write_versions(root: &Path, members: &[WorkspaceMember], entries: &[PlanEntry]) {
+    let manifest = |entry: &PlanEntry| entry.component.manifest.as_ref().map(|m| root.join(m));
+    let inherited: Vec<&PlanEntry> = entries
+        .iter()
+        .filter(|entry| manifest(entry).is_some_and(|manifest| inherits_version(&manifest)))
+        .collect();
+    let mut versions: Vec<Option<&Version>> =
+        inherited.iter().map(|entry| entry.next.as_ref()).collect();
+    versions.sort();
+    versions.dedup();
+    if versions.len() > 1 {
+        eprintln!("Crates inheriting the workspace version must be released together:");
+        for entry in &inherited {
+            let next = entry.next.as_ref().map(Version::to_string);
+            eprintln!(
+                "  {} {}",
+                entry.component.name,
+                next.as_deref().unwrap_or("unreleased")
+            );
+        }
+        std::process::exit(1);
+    }
+    if let Some(Some(version)) = versions.first() {
+        set_workspace_version(&root.join(MANIFEST_FILE_NAME), version);
+    }
+
+    let mut released: Vec<(String, Version)> = Vec::new();
+    for entry in entries {
+        let (next, manifest) = match (&entry.next, manifest(entry)) {
+            (Some(next), Some(manifest)) => (next, manifest),
+            _ => continue,
+        };
+        set_package_version(&manifest, next);
+        let name = package_name(&manifest).unwrap_or_else(|| entry.component.name.clone());
+        released.push((name, next.clone()));
+    }
+    let manifests = members
+        .iter()
+        .map(|member| root.join(member.manifest()))
+        .chain(std::iter::once(root.join(MANIFEST_FILE_NAME)));
+    for manifest in manifests {
+        update_requirements(&manifest, &released);
+    }
+}
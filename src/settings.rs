@@ -1,28 +1,157 @@
-use std::path::{self, PathBuf};
+use std::{
+    collections::BTreeMap,
+    path::{self, PathBuf},
+};
 
 use clap::ValueEnum as _;
 use regex::Regex;
+use semver::{Version, VersionReq};
 
 use crate::{
+    cargo::WorkspaceMember,
     cli::{
-        BuildMetadataOptions, BumpingOptions, Cli, Format, PreReleaseWithBumpArgs, PrereleaseArgs,
-        PrereleaseOptions, StandardBumpArgs, Strategy,
+        BranchCase, BuildMetadataOptions, BumpLevel, BumpingOptions, Cli, CommitConvention,
+        FieldSet, Format, PreReleaseWithBumpArgs, PrereleaseArgs, PrereleaseFilter,
+        PrereleaseOptions, ShallowPolicy, SignatureFormat, SquashMode, StandardBumpArgs, Strategy,
+        TagConflictPolicy, TagOrder, VersionSource,
     },
     config::Configuration,
     default,
+    version::SEMVER_REGEX,
 };
 #[derive(Debug)]
 pub struct FilterSettings {
     pub tag: Regex,
+    /// Tag template converted into a regular expression with a `version` capture group
+    pub template: Option<Regex>,
+    pub exclude: Vec<Regex>,
+    pub version: Option<VersionReq>,
+    pub prerelease: PrereleaseFilter,
+    pub conflict: TagConflictPolicy,
+    pub order: TagOrder,
+    pub annotated: bool,
+    pub signed: Option<SignatureFormat>,
+    /// GPG keyring used for verifying signatures instead of the default one
+    pub keyring: Option<PathBuf>,
+    /// SSH allowed signers file used for verifying signatures
+    pub allowed_signers: Option<PathBuf>,
 }
+impl FilterSettings {
+    /// Checks tag name against the include and exclude regular expressions
+    pub fn matches_tag(&self, tag_name: &str) -> bool {
+        self.tag.is_match(tag_name) && !self.exclude.iter().any(|re| re.is_match(tag_name))
+    }
+
+    /// Restricts selected tags to the `major.minor` line of given version
+    pub fn restrict_to_line(&mut self, base: &Version) {
+        let line = VersionReq::parse(&format!("~{}.{}", base.major, base.minor)).unwrap();
+        match &mut self.version {
+            Some(requirement) => requirement.comparators.extend(line.comparators),
+            None => self.version = Some(line),
+        }
+    }
+
+    /// Checks version against the version requirement and pre-release policy. The requirement
+    /// is matched against the release part only, pre-releases are handled by the policy
+    pub fn matches_version(&self, version: &Version) -> bool {
+        if let Some(requirement) = &self.version {
+            let release = Version::new(version.major, version.minor, version.patch);
+            if !requirement.matches(&release) {
+                return false;
+            }
+        }
+        match self.prerelease {
+            PrereleaseFilter::Include => true,
+            PrereleaseFilter::Exclude => version.pre.is_empty(),
+            PrereleaseFilter::Only => !version.pre.is_empty(),
+        }
+    }
+}
+/// Selection of commits counted in `{distance}` and analyzed for bumping
+#[derive(Debug)]
+pub struct CommitSettings {
+    /// Patterns matched against the commit subject
+    pub exclude: Vec<Regex>,
+    /// Only follow the first parent of merge commits
+    pub first_parent: bool,
+    /// Conventional Commit types accepted by the linter
+    pub types: Vec<String>,
+    /// Conventional Commit scopes accepted by the linter, any scope if empty
+    pub scopes: Vec<String>,
+    /// Conventional Commit types requiring a minor release
+    pub minor_types: Vec<String>,
+    /// Conventional Commit types requiring a patch release
+    pub patch_types: Vec<String>,
+    pub squash: SquashMode,
+    pub convention: CommitConvention,
+    /// Patterns of the `regex` convention with the bump level they imply
+    pub patterns: Vec<(BumpLevel, Regex)>,
+    /// Only commits of the component are considered when set
+    pub component: Option<ComponentSettings>,
+}
+impl CommitSettings {
+    pub fn is_excluded(&self, commit: &git2::Commit) -> bool {
+        let subject = commit.summary().unwrap_or_default();
+        self.exclude.iter().any(|re| re.is_match(subject))
+    }
+}
+
+/// Monorepo component, its commits are selected by touched paths or Conventional Commit scope
+#[derive(Debug, Clone)]
+pub struct ComponentSettings {
+    pub name: String,
+    /// Paths relative to the repository root
+    pub paths: Vec<PathBuf>,
+    pub scopes: Vec<String>,
+    /// Template for the component's tags, told apart by the component name
+    pub tag_template: String,
+    /// Components this component depends on, released along with them
+    pub dependencies: Vec<String>,
+    /// Cargo manifest relative to the repository root, its `path` dependencies are dependencies
+    pub manifest: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub struct VersionSettings {
+    pub source: VersionSource,
+    /// Pattern with `major` and `minor` (and optionally `patch`) capture groups
+    pub pattern: Regex,
+}
+impl VersionSettings {
+    /// Derives the base version from the branch name when the branch is the version source
+    pub fn branch_version(&self, branch: &str) -> Option<Version> {
+        if !matches!(self.source, VersionSource::Branch) {
+            return None;
+        }
+        let captures = self.pattern.captures(branch)?;
+        let number = |name: &str| {
+            captures
+                .name(name)
+                .and_then(|m| m.as_str().parse::<u64>().ok())
+        };
+        Some(Version::new(
+            number("major")?,
+            number("minor")?,
+            number("patch").unwrap_or(0),
+        ))
+    }
+}
+
 #[derive(Debug)]
 pub struct OutputSettings {
     pub format: Format,
     pub template: String,
+    pub fields: FieldSet,
 }
 #[derive(Debug)]
 pub struct BumpSettings {
     pub increment: u64,
+    /// While the major version is 0, bump minor for major and patch for minor changes
+    pub zero_major: bool,
+    /// Major changes may still release 1.0.0 in zero major mode
+    pub allow_major_from_zero: bool,
+    /// Version replacing the bumped one
+    pub set: Option<Version>,
 }
 #[derive(Debug)]
 pub struct PrereleaseSettings {
@@ -34,24 +163,56 @@ pub struct BuildMetadataSettings {
     pub template: String,
 }
 
+/// Rules for turning the branch name into a valid version identifier
+#[derive(Debug)]
+pub struct BranchNameSettings {
+    pub strip_prefixes: Vec<String>,
+    /// Literal substitutions applied before normalization, sorted by the searched text
+    pub replacements: Vec<(String, String)>,
+    pub case: BranchCase,
+    /// Longer names are truncated and suffixed with a hash of the full name
+    pub max_length: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct FallbackSettings {
+    pub manifest: Option<PathBuf>,
+}
+
 #[derive(Debug)]
 pub struct Settings {
     pub directory: PathBuf,
+    pub rev: Option<String>,
+    pub branch: Option<String>,
+    pub branch_name: BranchNameSettings,
+    pub shallow: ShallowPolicy,
     pub filter: FilterSettings,
+    pub commits: CommitSettings,
+    pub version: VersionSettings,
     pub output: OutputSettings,
     pub bump: BumpSettings,
     pub prerelease: PrereleaseSettings,
     pub build: BuildMetadataSettings,
+    pub fallback: FallbackSettings,
 }
 
 impl Settings {
-    pub fn merge(cli: &Cli, config: &Configuration) -> Self {
-        let command = match &cli.cmd {
+    /// Configuration section of the subcommand
+    fn command(cli: &Cli) -> &'static str {
+        match &cli.cmd {
             crate::Commands::Current { .. } => "current",
+            crate::Commands::Log { .. } => "log",
+            crate::Commands::Check { .. } => "check",
+            crate::Commands::LintCommit { .. } => "lint-commit",
+            crate::Commands::LintRange { .. } => "lint-range",
+            crate::Commands::Hooks { .. } => "hooks",
+            crate::Commands::Plan => "plan",
+            crate::Commands::Workspace { .. } => "workspace",
             crate::Commands::Next { strategy, .. } => match strategy {
                 Some(Strategy::Major { .. }) => "next.major",
                 Some(Strategy::Minor { .. }) => "next.minor",
                 Some(Strategy::Patch { .. }) => "next.patch",
+                Some(Strategy::Auto { .. }) => "next.auto",
                 Some(Strategy::Prerelease { .. }) => "next.prerelease",
                 Some(Strategy::PreMajor { .. }) => "next.pre-major",
                 Some(Strategy::PreMinor { .. }) => "next.pre-minor",
@@ -59,16 +220,66 @@ impl Settings {
                 Some(Strategy::Dev { .. }) => "next.dev",
                 None => "next.dev",
             },
-        };
+        }
+    }
 
-        let directory = path::absolute(match &cli.directory {
+    /// Directory of the repository to evaluate
+    pub fn directory(cli: &Cli, config: &Configuration) -> PathBuf {
+        let command = Self::command(cli);
+        path::absolute(match &cli.directory {
             Some(directory) => directory.clone(),
             None => match config.get::<String>(command, "directory") {
                 Ok(dir) => PathBuf::from(dir),
                 Err(_) => PathBuf::from(default::DIRECTORY),
             },
         })
-        .unwrap();
+        .unwrap()
+    }
+
+    /// Merges command line and configuration. Components without configuration are looked up in
+    /// the Cargo workspace `members`
+    pub fn merge(cli: &Cli, config: &Configuration, members: &[WorkspaceMember]) -> Self {
+        Self::merge_component(cli, config, members, None)
+    }
+
+    /// Merges the settings for the given component instead of the one selected by the command line
+    /// or configuration
+    pub fn merge_component(
+        cli: &Cli,
+        config: &Configuration,
+        members: &[WorkspaceMember],
+        component: Option<&str>,
+    ) -> Self {
+        let command = Self::command(cli);
+        let directory = Self::directory(cli, config);
+
+        // Only given on the command line, a stale config entry would pin every command
+        let rev = cli.rev.clone();
+
+        // Resolved before configuration rules are selected for it
+        let branch = config.branch().map(str::to_string);
+
+        let branch_strip_prefixes = config
+            .get::<Vec<String>>(command, "branch.strip_prefixes")
+            .unwrap_or_default();
+        let branch_replacements: Vec<(String, String)> = config
+            .get::<BTreeMap<String, String>>(command, "branch.replacements")
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        let branch_case = match config.get::<String>(command, "branch.case") {
+            Ok(case) => BranchCase::from_str(&case, true).unwrap(),
+            Err(_) => BranchCase::Lower,
+        };
+        let branch_max_length = config.get::<usize>(command, "branch.max_length").ok();
+
+        let shallow = match &cli.shallow {
+            Some(policy) => policy.clone(),
+            None => match config.get::<String>(command, "shallow") {
+                Ok(policy) => ShallowPolicy::from_str(&policy, true).unwrap(),
+                Err(_) => ShallowPolicy::Warn,
+            },
+        };
 
         let filter_tag = match &cli.filter.tag {
             Some(filter) => filter.clone(),
@@ -79,8 +290,175 @@ impl Settings {
         };
         let filter_tag = Regex::new(&filter_tag).unwrap();
 
+        let component = match component.or(cli.component.as_deref()) {
+            Some(component) => Some(component.to_string()),
+            None => config.get::<String>(command, "component").ok(),
+        };
+        let mut component =
+            component.map(|name| Settings::get_component(config, command, &name, members));
+
+        let mut tag_template = match &cli.filter.tag_template {
+            Some(template) => Some(template.clone()),
+            None => match &component {
+                Some(component) => Some(component.tag_template.clone()),
+                None => config.get::<String>(command, "tag.template").ok(),
+            },
+        };
+        // Templates of components only match their own tags, whatever source they come from
+        if let (Some(component), Some(template)) = (&mut component, &mut tag_template) {
+            *template = template.replace("{component}", &component.name);
+            component.tag_template = template.clone();
+        }
+        let tag_template = tag_template.map(|template| Settings::tag_template_regex(&template));
+
+        let filter_exclude = match &cli.filter.exclude {
+            Some(exclude) => exclude.clone(),
+            None => config
+                .get::<Vec<String>>(command, "filter.exclude")
+                .unwrap_or_default(),
+        };
+        let filter_exclude: Vec<Regex> = filter_exclude
+            .iter()
+            .map(|exclude| {
+                Regex::new(exclude).unwrap_or_else(|e| {
+                    eprintln!("Invalid tag exclude pattern \"{}\": {}", exclude, e);
+                    std::process::exit(1);
+                })
+            })
+            .collect();
+
+        let filter_version = match &cli.filter.version_requirement {
+            Some(requirement) => Some(requirement.clone()),
+            None => config.get::<String>(command, "filter.version").ok(),
+        };
+        let filter_version = filter_version.map(|requirement| {
+            VersionReq::parse(&requirement).unwrap_or_else(|e| {
+                eprintln!("Invalid version requirement \"{}\": {}!", requirement, e);
+                std::process::exit(1);
+            })
+        });
+
+        let filter_prerelease = match &cli.filter.prerelease {
+            Some(prerelease) => prerelease.clone(),
+            None => match config.get::<String>(command, "filter.prerelease") {
+                Ok(prerelease) => PrereleaseFilter::from_str(&prerelease, true).unwrap(),
+                Err(_) => PrereleaseFilter::Include,
+            },
+        };
+
+        let filter_conflict = match &cli.filter.conflict {
+            Some(conflict) => conflict.clone(),
+            None => match config.get::<String>(command, "filter.conflict") {
+                Ok(conflict) => TagConflictPolicy::from_str(&conflict, true).unwrap(),
+                Err(_) => TagConflictPolicy::PreferAnnotated,
+            },
+        };
+
+        let filter_order = match &cli.filter.order {
+            Some(order) => order.clone(),
+            None => match config.get::<String>(command, "filter.order") {
+                Ok(order) => TagOrder::from_str(&order, true).unwrap(),
+                Err(_) => TagOrder::Semver,
+            },
+        };
+
+        let filter_annotated = cli.filter.annotated
+            || config
+                .get::<bool>(command, "filter.annotated")
+                .unwrap_or(false);
+
+        let filter_signed = match &cli.filter.signed {
+            Some(format) => Some(format.clone()),
+            None => config
+                .get::<String>(command, "filter.signed")
+                .ok()
+                .map(|format| SignatureFormat::from_str(&format, true).unwrap()),
+        };
+        let filter_keyring = config
+            .get::<String>(command, "filter.keyring")
+            .ok()
+            .map(PathBuf::from);
+        let filter_allowed_signers = config
+            .get::<String>(command, "filter.allowed_signers")
+            .ok()
+            .map(PathBuf::from);
+
+        let commits_exclude: Vec<Regex> = config
+            .get::<Vec<String>>(command, "commits.exclude")
+            .unwrap_or_default()
+            .iter()
+            .map(|exclude| {
+                Regex::new(exclude).unwrap_or_else(|e| {
+                    eprintln!("Invalid commit exclude pattern \"{}\": {}", exclude, e);
+                    std::process::exit(1);
+                })
+            })
+            .collect();
+        let commits_first_parent = config
+            .get::<bool>(command, "commits.first_parent")
+            .unwrap_or(false);
+        let commits_types = config
+            .get::<Vec<String>>(command, "commits.types")
+            .unwrap_or_else(|_| default::COMMIT_TYPES.map(String::from).to_vec());
+        let commits_scopes = config
+            .get::<Vec<String>>(command, "commits.scopes")
+            .unwrap_or_default();
+        let commits_minor_types = config
+            .get::<Vec<String>>(command, "commits.minor_types")
+            .unwrap_or_else(|_| default::MINOR_TYPES.map(String::from).to_vec());
+        let commits_patch_types = config
+            .get::<Vec<String>>(command, "commits.patch_types")
+            .unwrap_or_else(|_| default::PATCH_TYPES.map(String::from).to_vec());
+
+        let commits_squash = match config.get::<String>(command, "commits.squash") {
+            Ok(squash) => SquashMode::from_str(&squash, true).unwrap(),
+            Err(_) => SquashMode::Title,
+        };
+
+        let commits_convention = match config.get::<String>(command, "commits.convention") {
+            Ok(convention) => CommitConvention::from_str(&convention, true).unwrap(),
+            Err(_) => CommitConvention::Conventional,
+        };
+        let mut commits_patterns: Vec<(BumpLevel, Regex)> = Vec::new();
+        for level in BumpLevel::value_variants() {
+            let name = level.to_possible_value().unwrap().get_name().to_string();
+            let patterns = config
+                .get::<Vec<String>>(command, &format!("commits.patterns.{}", name))
+                .unwrap_or_default();
+            for pattern in patterns {
+                let regex = Regex::new(&pattern).unwrap_or_else(|e| {
+                    eprintln!("Invalid commit pattern \"{}\": {}", pattern, e);
+                    std::process::exit(1);
+                });
+                commits_patterns.push((level.clone(), regex));
+            }
+        }
+
+        let version_source = match config.get::<String>(command, "version.source") {
+            Ok(source) => VersionSource::from_str(&source, true).unwrap(),
+            Err(_) => VersionSource::Tag,
+        };
+        let version_pattern = config
+            .get::<String>(command, "version.pattern")
+            .unwrap_or_else(|_| default::BRANCH_VERSION_PATTERN.to_string());
+        let version_pattern = Regex::new(&version_pattern).unwrap_or_else(|e| {
+            eprintln!(
+                "Invalid branch version pattern \"{}\": {}",
+                version_pattern, e
+            );
+            std::process::exit(1);
+        });
+
         let output_format = match &cli.output.format {
+            Some(Format::Plain) if cli.output.verbose_json => {
+                eprintln!(
+                    "--verbose-json requires JSON output, it cannot be used with --format plain!"
+                );
+                std::process::exit(1);
+            }
             Some(format) => format.clone(),
+            // Verbose JSON output implies the JSON format
+            None if cli.output.verbose_json => Format::Json,
             None => match config.get::<String>(command, "output.format") {
                 Ok(format) => Format::from_str(&format, true).unwrap(),
                 Err(_) => Format::Plain,
@@ -95,6 +473,29 @@ impl Settings {
             },
         };
 
+        let output_fields = if cli.output.verbose_json {
+            FieldSet::All
+        } else {
+            match config.get::<String>(command, "output.fields") {
+                Ok(fields) => FieldSet::from_str(&fields, true).unwrap(),
+                Err(_) => FieldSet::Default,
+            }
+        };
+
+        let set = match &cli.cmd {
+            crate::Commands::Next { set, .. } => set.clone(),
+            _ => None,
+        };
+
+        let zero_major = config
+            .get::<bool>(command, "zero_major_mode")
+            .unwrap_or(false);
+
+        let fallback_manifest = config
+            .get::<String>(command, "fallback.manifest")
+            .ok()
+            .map(PathBuf::from);
+
         let mut increment: u64 = default::INCREMENT;
 
         let mut prerelease_identifier: Option<String> =
@@ -102,9 +503,8 @@ impl Settings {
         let mut prerelease_template: String = default::PRERELEASE_TEMPLATE.to_string();
         let mut build_metadata_template: String = default::BUILD_METADATA_TEMPLATE.to_string();
 
-        match &cli.cmd {
-            crate::cli::Commands::Current { field: _ } => {}
-            crate::cli::Commands::Next { strategy, field: _ } => match strategy {
+        if let crate::cli::Commands::Next { strategy, .. } = &cli.cmd {
+            match strategy {
                 Some(strategy) => match strategy {
                     Strategy::Major(StandardBumpArgs {
                         bump_options,
@@ -131,6 +531,10 @@ impl Settings {
                     Strategy::Patch(StandardBumpArgs {
                         bump_options,
                         build_metadata_options,
+                    })
+                    | Strategy::Auto(StandardBumpArgs {
+                        bump_options,
+                        build_metadata_options,
                     }) => {
                         increment = Settings::get_increment(config, bump_options, command);
                         build_metadata_template = Settings::get_build_metadata_template(
@@ -245,17 +649,58 @@ impl Settings {
                     build_metadata_template =
                         Settings::get_build_metadata_template(config, &b, command);
                 }
-            },
+            }
         }
-        Self {
+        let mut settings = Self {
             directory,
-            filter: FilterSettings { tag: filter_tag },
+            rev,
+            branch,
+            branch_name: BranchNameSettings {
+                strip_prefixes: branch_strip_prefixes,
+                replacements: branch_replacements,
+                case: branch_case,
+                max_length: branch_max_length,
+            },
+            shallow,
+            filter: FilterSettings {
+                tag: filter_tag,
+                template: tag_template,
+                exclude: filter_exclude,
+                version: filter_version,
+                prerelease: filter_prerelease,
+                conflict: filter_conflict,
+                order: filter_order,
+                annotated: filter_annotated,
+                signed: filter_signed,
+                keyring: filter_keyring,
+                allowed_signers: filter_allowed_signers,
+            },
+            commits: CommitSettings {
+                exclude: commits_exclude,
+                first_parent: commits_first_parent,
+                types: commits_types,
+                scopes: commits_scopes,
+                minor_types: commits_minor_types,
+                patch_types: commits_patch_types,
+                squash: commits_squash,
+                convention: commits_convention,
+                patterns: commits_patterns,
+                component,
+            },
+            version: VersionSettings {
+                source: version_source,
+                pattern: version_pattern,
+            },
             output: OutputSettings {
                 format: output_format,
                 template: output_template,
+                fields: output_fields,
             },
             bump: BumpSettings {
-                increment: increment,
+                increment,
+                zero_major,
+                allow_major_from_zero: cli.allow_major_from_zero,
+                set,
             },
             prerelease: PrereleaseSettings {
                 identifier: prerelease_identifier,
@@ -264,6 +709,96 @@ impl Settings {
             build: BuildMetadataSettings {
                 template: build_metadata_template,
             },
+            fallback: FallbackSettings {
+                manifest: fallback_manifest,
+            },
+        };
+        // Release branches pin the version line, tags of other lines are ignored
+        if let Some(base) = settings.branch_version() {
+            settings.filter.restrict_to_line(&base);
+        }
+        settings
+    }
+
+    /// Version line of the release branch, if versions are derived from branch names
+    pub fn branch_version(&self) -> Option<Version> {
+        self.branch
+            .as_deref()
+            .and_then(|branch| self.version.branch_version(branch))
+    }
+
+    /// Converts tag template (e.g. `{component}-v{version}`) into an anchored regular expression.
+    /// `{version}` becomes a `version` capture group, any other variable matches anything
+    fn tag_template_regex(template: &str) -> Regex {
+        if template.matches("{version}").count() != 1 {
+            eprintln!(
+                "Tag template \"{}\" must contain variable {{version}} exactly once",
+                template
+            );
+            std::process::exit(1);
+        }
+        let variable = Regex::new(r"\{[a-z_]+\}").unwrap();
+        let mut pattern = String::from("^");
+        let mut last = 0;
+        for m in variable.find_iter(template) {
+            pattern.push_str(&regex::escape(&template[last..m.start()]));
+            if m.as_str() == "{version}" {
+                pattern.push_str(&format!("(?P<version>{})", SEMVER_REGEX.as_str()));
+            } else {
+                pattern.push_str(".+?");
+            }
+            last = m.end();
+        }
+        pattern.push_str(&regex::escape(&template[last..]));
+        pattern.push('$');
+        Regex::new(&pattern).unwrap()
+    }
+
+    /// Reads the component configuration. A component without configuration is the Cargo
+    /// workspace member of the same name or, if there is none, matched by its name as commit scope
+    fn get_component(
+        config: &Configuration,
+        command: &str,
+        name: &str,
+        members: &[WorkspaceMember],
+    ) -> ComponentSettings {
+        let mut paths: Vec<PathBuf> = config
+            .get::<Vec<String>>(command, &format!("components.{}.paths", name))
+            .unwrap_or_default()
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+        let mut scopes = config
+            .get::<Vec<String>>(command, &format!("components.{}.scopes", name))
+            .unwrap_or_default();
+        let mut manifest = config
+            .get::<String>(command, &format!("components.{}.manifest", name))
+            .ok()
+            .map(PathBuf::from);
+        if paths.is_empty() && scopes.is_empty() {
+            match members.iter().find(|member| member.name == name) {
+                Some(member) => {
+                    manifest = manifest.or_else(|| Some(member.manifest()));
+                    paths.push(member.path.clone());
+                }
+                None => scopes.push(name.to_string()),
+            }
+        }
+        let tag_template = config
+            .get::<String>(command, &format!("components.{}.tag_template", name))
+            .or_else(|_| config.get::<String>(command, "tag.template"))
+            .unwrap_or_else(|_| default::COMPONENT_TAG_TEMPLATE.to_string())
+            .replace("{component}", name);
+        let dependencies = config
+            .get::<Vec<String>>(command, &format!("components.{}.dependencies", name))
+            .unwrap_or_default();
+        ComponentSettings {
+            name: name.to_string(),
+            paths,
+            scopes,
+            tag_template,
+            dependencies,
+            manifest,
         }
     }
 
@@ -312,6 +847,7 @@ impl Settings {
                     } else if command == "next.major"
                         || command == "next.minor"
                         || command == "next.patch"
+                        || command == "next.auto"
                     {
                         default::PRERELEASE_TEMPLATE.to_string()
                     } else {
@@ -350,5 +886,14 @@ impl Settings {
             );
             std::process::exit(1);
         }
+        let pattern = &self.version.pattern;
+        let names: Vec<&str> = pattern.capture_names().flatten().collect();
+        if !names.contains(&"major") || !names.contains(&"minor") {
+            eprintln!(
+                "Branch version pattern \"{}\" is missing required capture groups \"major\" and \"minor\"",
+                pattern
+            );
+            std::process::exit(1);
+        }
     }
 }
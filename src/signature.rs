@@ -0,0 +1,144 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use git2::{Oid, Repository};
+
+use crate::{cli::SignatureFormat, settings::FilterSettings};
+
+static PGP_SIGNATURE_MARKER: &str = "-----BEGIN PGP SIGNATURE-----";
+static SSH_SIGNATURE_MARKER: &str = "-----BEGIN SSH SIGNATURE-----";
+
+/// Detached signature of an annotated tag together with the signed payload
+struct TagSignature {
+    format: SignatureFormat,
+    payload: Vec<u8>,
+    signature: Vec<u8>,
+}
+
+fn extract_signature(repo: &Repository, tag_id: Oid) -> Option<TagSignature> {
+    let odb = repo.odb().ok()?;
+    let object = odb.read(tag_id).ok()?;
+    let data = std::str::from_utf8(object.data()).ok()?;
+
+    // Like git, the last signature block is the signature, earlier ones belong to the message
+    let (format, start) = match (
+        data.rfind(PGP_SIGNATURE_MARKER),
+        data.rfind(SSH_SIGNATURE_MARKER),
+    ) {
+        (Some(pgp), Some(ssh)) if ssh > pgp => (SignatureFormat::Ssh, ssh),
+        (Some(pgp), _) => (SignatureFormat::Gpg, pgp),
+        (None, Some(ssh)) => (SignatureFormat::Ssh, ssh),
+        (None, None) => return None,
+    };
+    Some(TagSignature {
+        format,
+        payload: data.as_bytes()[..start].to_vec(),
+        signature: data.as_bytes()[start..].to_vec(),
+    })
+}
+
+/// Verifies the annotated tag's signature against local key material only. Lightweight and
+/// unsigned tags, as well as signatures in other than the requested format, are rejected
+pub fn verify_tag(
+    repo: &Repository,
+    tag_id: Oid,
+    requested: &SignatureFormat,
+    filter: &FilterSettings,
+) -> bool {
+    let signature = match extract_signature(repo, tag_id) {
+        Some(signature) => signature,
+        None => return false,
+    };
+    // Created exclusively with owner-only permissions and removed when dropped
+    let mut signature_file = match tempfile::Builder::new().prefix("doxxer-").tempfile() {
+        Ok(file) => file,
+        Err(_) => return false,
+    };
+    if signature_file.write_all(&signature.signature).is_err() || signature_file.flush().is_err() {
+        return false;
+    }
+
+    let verified = match (&signature.format, requested) {
+        (SignatureFormat::Gpg, SignatureFormat::Gpg | SignatureFormat::Any) => {
+            verify_gpg(&signature, signature_file.path(), filter.keyring.as_deref())
+        }
+        (SignatureFormat::Ssh, SignatureFormat::Ssh | SignatureFormat::Any) => {
+            match &filter.allowed_signers {
+                Some(allowed_signers) => {
+                    verify_ssh(&signature, signature_file.path(), allowed_signers)
+                }
+                None => false,
+            }
+        }
+        _ => false,
+    };
+    let _ = signature_file.close();
+    verified
+}
+
+fn verify_gpg(signature: &TagSignature, signature_file: &Path, keyring: Option<&Path>) -> bool {
+    let mut command = Command::new("gpg");
+    command.args(["--batch", "--no-tty", "--status-fd", "1"]);
+    if let Some(keyring) = keyring {
+        command
+            .arg("--no-default-keyring")
+            .arg("--keyring")
+            .arg(absolute(keyring));
+    }
+    command.arg("--verify").arg(signature_file).arg("-");
+
+    match run_with_payload(command, &signature.payload) {
+        Some(output) => {
+            output.status.success()
+                && String::from_utf8_lossy(&output.stdout).contains("[GNUPG:] GOODSIG")
+        }
+        None => false,
+    }
+}
+
+fn verify_ssh(signature: &TagSignature, signature_file: &Path, allowed_signers: &Path) -> bool {
+    let allowed_signers = absolute(allowed_signers);
+
+    let mut find_principals = Command::new("ssh-keygen");
+    find_principals
+        .args(["-Y", "find-principals", "-f"])
+        .arg(&allowed_signers)
+        .arg("-s")
+        .arg(signature_file);
+    let principals = match run_with_payload(find_principals, &[]) {
+        Some(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        }
+        _ => return false,
+    };
+
+    principals.lines().filter(|p| !p.is_empty()).any(|principal| {
+        let mut verify = Command::new("ssh-keygen");
+        verify
+            .args(["-Y", "verify", "-n", "git", "-f"])
+            .arg(&allowed_signers)
+            .arg("-I")
+            .arg(principal)
+            .arg("-s")
+            .arg(signature_file);
+        matches!(run_with_payload(verify, &signature.payload), Some(output) if output.status.success())
+    })
+}
+
+fn run_with_payload(mut command: Command, payload: &[u8]) -> Option<std::process::Output> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+    child.stdin.take()?.write_all(payload).ok()?;
+    child.wait_with_output().ok()
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -9,6 +9,9 @@ pub struct TemplateVariables {
     pub distance: usize,
     pub date_time: DateTime<Utc>,
     pub branch: String,
+    pub pr: String,
+    pub build_number: String,
+    pub ci: String,
 }
 impl TemplateVariables {
     fn fields(&self) -> Vec<(&'static str, String)> {
@@ -23,6 +26,9 @@ impl TemplateVariables {
                 self.date_time.format("%Y%m%dT%H%M%SZ").to_string(),
             ),
             ("{branch}", self.branch.clone()),
+            ("{pr}", self.pr.clone()),
+            ("{build_number}", self.build_number.clone()),
+            ("{ci}", self.ci.clone()),
         ]
     }
 
@@ -1,23 +1,82 @@
-use chrono::Utc;
-use git2::{Error, ObjectType, Repository};
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use clap::ValueEnum as _;
+use git2::{BranchType, Commit, Error, ObjectType, Oid, Repository, RepositoryOpenFlags};
 use once_cell::sync::Lazy;
 use semver::{BuildMetadata, Prerelease, Version};
 use serde_json::{json, Value};
 
 use crate::{
-    cli::{Field, Format},
-    settings::Settings,
+    cargo::{manifest_version, MANIFEST_FILE_NAME},
+    ci::{self, CiEnvironment},
+    cli::{BranchCase, BumpLevel, Field, FieldSet, Format, TagConflictPolicy, TagOrder},
+    commits::{commits_since, required_release, CommitRange},
+    settings::{BranchNameSettings, ComponentSettings, FilterSettings, OutputSettings, Settings},
+    signature::verify_tag,
     template::TemplateVariables,
     Strategy,
 };
 
 use regex::Regex;
 
-static SEMVER_REGEX: Lazy<Regex> = Lazy::new(|| {
+pub static SEMVER_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?P<major>0|[1-9]\d*)\.(?P<minor>0|[1-9]\d*)\.(?P<patch>0|[1-9]\d*)(?:-(?P<prerelease>(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9]\d*|\d*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+(?P<buildmetadata>[0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?").unwrap()
 });
 
-fn get_current_branch_name(repo: &Repository) -> Result<Option<String>, Error> {
+/// Opens the repository containing the given path, searching parent directories and honouring
+/// `GIT_DIR`, `GIT_WORK_TREE` and `GIT_CEILING_DIRECTORIES` like Git itself
+pub fn discover_repository(path: &Path) -> Result<Repository, Error> {
+    if env::var_os("GIT_DIR").is_some() {
+        return Repository::open_from_env();
+    }
+    let ceiling_dirs: Vec<PathBuf> = match env::var_os("GIT_CEILING_DIRECTORIES") {
+        Some(dirs) => env::split_paths(&dirs).collect(),
+        None => Vec::new(),
+    };
+    let repo = Repository::open_ext(path, RepositoryOpenFlags::empty(), &ceiling_dirs)?;
+    if let Some(work_tree) = env::var_os("GIT_WORK_TREE") {
+        repo.set_workdir(Path::new(&work_tree), false)?;
+    }
+    Ok(repo)
+}
+
+/// Returns the discovered repository or exits with an error message
+pub fn open_repository(repo: Result<Repository, Error>) -> Repository {
+    match repo {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Issue opening repository: {}!", e.message());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Resolves a revision specification to a commit. Returns `None` when HEAD should be used, tags
+/// are then selected from the whole repository
+pub fn resolve_revision(repo: &Repository, rev: Option<&str>) -> Result<Option<Oid>, Error> {
+    match rev {
+        Some(rev) => {
+            let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+            Ok(Some(commit.id()))
+        }
+        None => Ok(None),
+    }
+}
+
+pub fn get_current_branch_name(
+    repo: &Repository,
+    rev: Option<&str>,
+) -> Result<Option<String>, Error> {
+    if let Some(rev) = rev.filter(|rev| *rev != "HEAD") {
+        return Ok(repo
+            .find_branch(rev, BranchType::Local)
+            .ok()
+            .and_then(|branch| branch.name().ok().flatten().map(|s| s.to_string())));
+    }
     let head = repo.head()?;
     if head.is_branch() {
         let branch_name_shorthand = head.shorthand();
@@ -31,24 +90,37 @@ fn get_current_branch_name(repo: &Repository) -> Result<Option<String>, Error> {
     }
 }
 
-fn normalize_branch_name_for_semver(branch_name: &str) -> String {
+/// Length of the hash appended to truncated branch names, including the separator
+const BRANCH_HASH_SUFFIX_LENGTH: usize = 8;
+
+fn normalize_branch_name_for_semver(branch_name: &str, settings: &BranchNameSettings) -> String {
+    let mut branch = branch_name;
+    if let Some(prefix) = settings
+        .strip_prefixes
+        .iter()
+        .find(|prefix| branch.starts_with(prefix.as_str()))
+    {
+        branch = &branch[prefix.len()..];
+    }
+    let mut branch = branch.to_string();
+    for (from, to) in &settings.replacements {
+        branch = branch.replace(from, to);
+    }
+
     let mut normalized_chars: Vec<char> = Vec::new();
     let mut last_char_was_hyphen = false;
 
-    for c in branch_name.chars() {
+    for c in branch.chars() {
         if c.is_ascii_alphanumeric() {
-            normalized_chars.push(c.to_ascii_lowercase());
+            normalized_chars.push(match settings.case {
+                BranchCase::Lower => c.to_ascii_lowercase(),
+                BranchCase::Upper => c.to_ascii_uppercase(),
+                BranchCase::Preserve => c,
+            });
             last_char_was_hyphen = false;
-        } else if c == '-' {
-            if !last_char_was_hyphen {
-                normalized_chars.push('-');
-                last_char_was_hyphen = true;
-            }
-        } else {
-            if !last_char_was_hyphen {
-                normalized_chars.push('-');
-                last_char_was_hyphen = true;
-            }
+        } else if !last_char_was_hyphen {
+            normalized_chars.push('-');
+            last_char_was_hyphen = true;
         }
     }
     let mut normalized_name: String = normalized_chars.into_iter().collect();
@@ -59,73 +131,293 @@ fn normalize_branch_name_for_semver(branch_name: &str) -> String {
         normalized_name.pop();
     }
 
-    normalized_name
+    match settings.max_length {
+        Some(max_length) if normalized_name.len() > max_length => {
+            // Hash of the full branch name keeps truncated names stable and distinguishable
+            // Leading letter keeps it from becoming a numeric identifier with leading zeros
+            let hash = Oid::hash_object(ObjectType::Blob, branch_name.as_bytes())
+                .map(|oid| format!("h{}", oid))
+                .unwrap_or_default();
+            if max_length < BRANCH_HASH_SUFFIX_LENGTH {
+                return hash[..max_length].to_string();
+            }
+            let prefix =
+                normalized_name[..max_length - BRANCH_HASH_SUFFIX_LENGTH].trim_end_matches('-');
+            let hash = &hash[..BRANCH_HASH_SUFFIX_LENGTH - 1];
+            if prefix.is_empty() {
+                hash.to_string()
+            } else {
+                format!("{}-{}", prefix, hash)
+            }
+        }
+        _ => normalized_name,
+    }
+}
+
+fn get_target_commit(repo: &Repository, target: Option<Oid>) -> Result<Commit<'_>, Error> {
+    if let Some(oid) = target {
+        return repo.find_commit(oid);
+    }
+    let head = repo.head()?;
+    let commit = head.peel(ObjectType::Commit)?;
+    commit
+        .into_commit()
+        .map_err(|_| Error::from_str("HEAD is not a commit"))
+}
+
+fn get_commit_date(commit: &Commit) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(commit.time().seconds(), 0)
 }
 
-fn find_tag_name_matching_version(
+/// Repository facts about the evaluated commit, shared by templates and output
+#[derive(Debug, Default)]
+pub struct VersionContext {
+    pub sha: String,
+    pub distance: usize,
+    pub branch: String,
+    pub commit_date: Option<DateTime<Utc>>,
+    pub shallow: bool,
+    /// Other tags for the current version pointing at different commits
+    pub conflicts: Vec<String>,
+    /// Tag the current version originates from
+    pub tag: Option<String>,
+    /// Policy used for selecting the tag
+    pub order: Option<TagOrder>,
+    pub ci: Option<CiEnvironment>,
+    /// Number of commits since the tag left out by the commit filters
+    pub excluded: usize,
+    /// Version derived from the branch name, used instead of the latest tag
+    pub base: Option<Version>,
+    /// Version requested by commit trailers since the latest tag
+    pub release_as: Option<Version>,
+    /// Bump level required by the commits since the latest tag
+    pub bump: Option<BumpLevel>,
+}
+impl VersionContext {
+    pub fn short_sha(&self) -> &str {
+        &self.sha[..self.sha.len().min(7)]
+    }
+}
+
+/// Builds the context from the commits since the latest tag, `range` is collected once by the
+/// caller and shared with the bump inference
+fn version_context(
     repo: &Repository,
-    version_string: &str,
-    filter: &Regex,
-) -> Result<Option<String>, Error> {
-    let search_term = format!("{}{}", filter, version_string);
-    let mut matching_tag_name: Option<String> = None;
-    for tag_name_result in repo.tag_names(None)?.iter() {
-        if let Some(tag_name) = tag_name_result {
-            if tag_name == search_term {
-                matching_tag_name = Some(format!("refs/tags/{}", tag_name));
-                break;
-            }
-        }
+    settings: &Settings,
+    target: Option<Oid>,
+    latest: Option<&LatestTag>,
+    range: &CommitRange,
+) -> VersionContext {
+    let conflicts: Vec<String> = match latest {
+        Some(latest) => latest.conflicts.iter().map(|t| t.name.clone()).collect(),
+        None => Vec::new(),
+    };
+    let tag = latest.map(|latest| latest.tag.name.clone());
+    let order = Some(settings.filter.order.clone());
+    let ci = ci::detect();
+    let branch = settings.branch.clone().unwrap_or_default();
+    let commit = get_target_commit(repo, target).ok();
+
+    VersionContext {
+        sha: commit
+            .as_ref()
+            .map(|commit| commit.id().to_string())
+            .unwrap_or_default(),
+        distance: range.commits.len(),
+        excluded: range.excluded,
+        branch,
+        commit_date: commit.as_ref().and_then(get_commit_date),
+        shallow: repo.is_shallow(),
+        conflicts,
+        tag,
+        order,
+        ci,
+        base: None,
+        release_as: None,
+        bump: None,
     }
-    Ok(matching_tag_name)
 }
 
-fn get_commit_count_since_tag(repo: &Repository, tag_name: Option<&str>) -> Result<usize, Error> {
-    if let Some(tag) = tag_name {
-        format!("{}..HEAD", tag)
-    } else {
-        "HEAD".to_string()
+/// Tag carrying a SemVer version
+#[derive(Debug, Clone)]
+pub struct VersionTag {
+    pub name: String,
+    /// Tag object, or the commit itself for lightweight tags
+    pub id: Oid,
+    pub version: Version,
+    /// Commit the tag points to
+    pub commit: Oid,
+    pub annotated: bool,
+    /// Tagger time for annotated tags, commit time for lightweight ones
+    pub time: i64,
+}
+
+/// Selected version tag with other tags for the same version pointing at different commits
+#[derive(Debug)]
+pub struct LatestTag {
+    pub tag: VersionTag,
+    pub conflicts: Vec<VersionTag>,
+}
+
+/// Extracts version from the tag name. Uses the tag template or the `version` capture group of
+/// the tag filter when available, otherwise the first SemVer-looking part of the tag name
+pub fn parse_tag_version(filter: &FilterSettings, tag_name: &str) -> Option<Version> {
+    if !filter.matches_tag(tag_name) {
+        return None;
+    }
+    let matched = match &filter.template {
+        Some(template) => template.captures(tag_name)?.name("version")?,
+        None => match filter.tag.captures(tag_name)?.name("version") {
+            Some(version) => version,
+            None => SEMVER_REGEX.find(tag_name)?,
+        },
     };
-    let mut revwalk = repo.revwalk()?;
-    revwalk.push_head()?;
-    if let Some(tag) = tag_name {
-        if let Ok(obj) = repo.revparse_single(tag) {
-            revwalk.hide(obj.id())?;
-        }
+    let version = Version::parse(matched.as_str()).ok()?;
+    if filter.matches_version(&version) {
+        Some(version)
+    } else {
+        None
     }
+}
 
-    Ok(revwalk.count())
+/// Checks whether the commit is reachable from the target commit
+fn is_reachable(repo: &Repository, commit: Oid, target: Oid) -> bool {
+    commit == target || repo.graph_descendant_of(target, commit).unwrap_or(false)
 }
 
-fn get_short_head_hash(repo: &Repository) -> Result<String, Error> {
-    let head = repo.head()?;
-    let commit = head.peel(ObjectType::Commit)?;
-    let id = commit.id();
-    Ok(id.to_string()[..7].to_string())
+fn is_same_version(a: &Version, b: &Version) -> bool {
+    a.major == b.major && a.minor == b.minor && a.patch == b.patch && a.pre == b.pre
 }
 
-fn find_latest_semver(repo: &Repository, filter: &Regex) -> Result<Option<Version>, Error> {
-    let mut versions: Vec<Version> = Vec::new();
-    let _ = repo.tag_foreach(|_id, name_bytes| {
-        if let Ok(name) = String::from_utf8(name_bytes.to_vec()) {
-            if let Some(tag_name) = name.strip_prefix("refs/tags/") {
-                if filter.is_match(tag_name) {
-                    if let Some(captures) = SEMVER_REGEX.find(tag_name) {
-                        let matched_str = captures.as_str();
-                        if let Ok(version) = Version::parse(matched_str) {
-                            versions.push(version);
-                        }
-                    }
-                }
+/// Picks one of the tags for the same version according to the conflict policy
+fn resolve_conflict(
+    mut candidates: Vec<VersionTag>,
+    policy: &TagConflictPolicy,
+) -> Result<LatestTag, Error> {
+    match policy {
+        TagConflictPolicy::PreferAnnotated => {
+            candidates.sort_by(|a, b| b.annotated.cmp(&a.annotated).then(a.time.cmp(&b.time)))
+        }
+        TagConflictPolicy::PreferOldest => candidates.sort_by_key(|tag| tag.time),
+        TagConflictPolicy::Error => {}
+    }
+    let tag = candidates.remove(0);
+    let conflicts: Vec<VersionTag> = candidates
+        .into_iter()
+        .filter(|other| other.commit != tag.commit)
+        .collect();
+
+    if let TagConflictPolicy::Error = policy {
+        if !conflicts.is_empty() {
+            let names: Vec<String> = std::iter::once(&tag)
+                .chain(conflicts.iter())
+                .map(|t| format!("{} ({})", t.name, &t.commit.to_string()[..7]))
+                .collect();
+            return Err(Error::from_str(&format!(
+                "tags for version {} point to different commits: {}",
+                tag.version,
+                names.join(", ")
+            )));
+        }
+    }
+    Ok(LatestTag { tag, conflicts })
+}
+
+pub fn find_latest_semver(
+    repo: &Repository,
+    filter: &FilterSettings,
+    target: Option<Oid>,
+) -> Result<Option<LatestTag>, Error> {
+    let mut tags: Vec<VersionTag> = Vec::new();
+    repo.tag_foreach(|id, name_bytes| {
+        let name = match std::str::from_utf8(name_bytes) {
+            Ok(name) => name,
+            Err(_) => return true,
+        };
+        let tag_name = match name.strip_prefix("refs/tags/") {
+            Some(tag_name) => tag_name,
+            None => return true,
+        };
+        let version = match parse_tag_version(filter, tag_name) {
+            Some(version) => version,
+            None => return true,
+        };
+        let commit = match repo.find_object(id, None).and_then(|o| o.peel_to_commit()) {
+            Ok(commit) => commit,
+            Err(_) => return true,
+        };
+        if let Some(target) = target {
+            if !is_reachable(repo, commit.id(), target) {
+                return true;
             }
         }
-        return true;
-    });
+        let annotation = repo.find_tag(id).ok();
+        if filter.annotated && annotation.is_none() {
+            return true;
+        }
+        let time = match annotation.as_ref().and_then(|tag| tag.tagger()) {
+            Some(tagger) => tagger.when().seconds(),
+            None => commit.time().seconds(),
+        };
+        tags.push(VersionTag {
+            name: tag_name.to_string(),
+            id,
+            version,
+            commit: commit.id(),
+            annotated: annotation.is_some(),
+            time,
+        });
+        true
+    })?;
 
-    versions.sort();
-    versions.reverse();
+    // Sort by name as well so that the selection does not depend on iteration order
+    tags.sort_by(|a, b| b.version.cmp(&a.version).then(a.name.cmp(&b.name)));
+    // Signatures are checked only for the tags that could be selected, as verifying spawns gpg
+    // or ssh-keygen
+    let verified = |tag: &VersionTag| match &filter.signed {
+        Some(format) => verify_tag(repo, tag.id, format, filter),
+        None => true,
+    };
+    let index = match filter.order {
+        TagOrder::Semver => tags.iter().position(verified),
+        TagOrder::TaggerDate => {
+            tags.sort_by_key(|tag| std::cmp::Reverse(tag.time));
+            tags.iter().position(verified)
+        }
+        TagOrder::Topology => {
+            // Like `git describe`, the closest commit on the first-parent history with a
+            // verified tag wins
+            let mut revwalk = repo.revwalk()?;
+            match target {
+                Some(oid) => revwalk.push(oid)?,
+                None => revwalk.push_head()?,
+            }
+            revwalk.simplify_first_parent()?;
+            let mut index = None;
+            for oid in revwalk {
+                let oid = oid?;
+                index = tags
+                    .iter()
+                    .position(|tag| tag.commit == oid && verified(tag));
+                if index.is_some() {
+                    break;
+                }
+            }
+            index
+        }
+    };
+    let latest = match index {
+        Some(index) => tags.remove(index),
+        None => return Ok(None),
+    };
+    // Same-version tags on other commits are conflicts even when the ordering is topological
+    let mut candidates: Vec<VersionTag> = tags
+        .into_iter()
+        .filter(|tag| is_same_version(&tag.version, &latest.version) && verified(tag))
+        .collect();
+    candidates.insert(0, latest);
 
-    Ok(versions.into_iter().next())
+    resolve_conflict(candidates, &filter.conflict).map(Some)
 }
 
 pub fn extract_prerelease_identifier(pre: &Prerelease) -> Option<String> {
@@ -147,9 +439,8 @@ fn get_inc(pre: &str, identifier: &str) -> usize {
         return 1;
     }
 
-    if pre.starts_with(identifier) {
+    if let Some(suffix) = pre.strip_prefix(identifier) {
         // Current pre-release starts with the target identifier
-        let suffix = &pre[identifier.len()..];
         if suffix.is_empty() {
             return 2; // Identifier matches, no number, next is 2
         }
@@ -157,7 +448,7 @@ fn get_inc(pre: &str, identifier: &str) -> usize {
         // Try to parse a numeric suffix from the end
         let mut numeric_part = String::new();
         for char in suffix.chars().rev() {
-            if char.is_digit(10) {
+            if char.is_ascii_digit() {
                 numeric_part.insert(0, char);
             } else {
                 break; // Stop when a non-digit is encountered
@@ -166,65 +457,82 @@ fn get_inc(pre: &str, identifier: &str) -> usize {
 
         if !numeric_part.is_empty() {
             if let Ok(n) = numeric_part.parse::<usize>() {
-                return n + 1;
+                n + 1
             } else {
                 // Parsing failed, but we found digits, so assume it was 1 initially
-                return 2;
+                2
             }
         } else {
             // Identifier matches, but no numeric suffix found
-            return 2;
+            2
         }
     } else {
         // Identifier does not match, start at 1
-        return 1;
+        1
     }
 }
 
-pub fn next_version(repo: &Repository, strategy: &Strategy, settings: &Settings) -> Version {
-    let latest = current_version(repo, &settings.filter.tag);
-
-    let latest_tag_name =
-        match find_tag_name_matching_version(repo, &latest.to_string(), &settings.filter.tag) {
-            Ok(tag) => tag,
-            Err(_) => None,
-        };
-    let commit_count = match get_commit_count_since_tag(repo, latest_tag_name.as_deref()) {
-        Ok(count) => count,
-        Err(_) => 0,
-    };
-    let short_hash = match get_short_head_hash(repo) {
-        Ok(hash) => hash,
-        Err(_) => String::from(""),
-    };
-
+pub fn next_version(
+    latest: Version,
+    strategy: &Strategy,
+    settings: &Settings,
+    context: &VersionContext,
+) -> Version {
     let date_time = Utc::now();
-    let branch = get_current_branch_name(repo).unwrap().unwrap_or_default();
-    let branch = normalize_branch_name_for_semver(&branch);
+    let branch = normalize_branch_name_for_semver(&context.branch, &settings.branch_name);
 
     let mut next = latest;
 
-    // Set new major/minor/patch versions
-    match strategy {
-        Strategy::Major(_) | Strategy::PreMajor(_) => {
+    // Requested version replaces the bumped one, only complete versions are used verbatim
+    let requested = settings.bump.set.as_ref().or(context.release_as.as_ref());
+    if let Some(requested) = requested {
+        if !requested.pre.is_empty() || !requested.build.is_empty() {
+            return requested.clone();
+        }
+        next = requested.clone();
+    }
+    let zero_major = settings.bump.zero_major && next.major == 0;
+
+    let level = match strategy {
+        Strategy::Major(_) | Strategy::PreMajor(_) => Some(BumpLevel::Major),
+        Strategy::Minor(_) | Strategy::PreMinor(_) => Some(BumpLevel::Minor),
+        Strategy::Patch(_) | Strategy::PrePatch(_) => Some(BumpLevel::Patch),
+        // Commits since the latest tag decide, defaulting to patch
+        Strategy::Auto(_) => Some(context.bump.clone().unwrap_or(BumpLevel::Patch)),
+        Strategy::Prerelease(_) | Strategy::Dev(_) => None,
+    };
+    // Breaking changes only bump minor and features only patch before 1.0.0
+    let level = match level {
+        Some(BumpLevel::Major) if zero_major && !settings.bump.allow_major_from_zero => {
+            Some(BumpLevel::Minor)
+        }
+        Some(BumpLevel::Minor) if zero_major => Some(BumpLevel::Patch),
+        level => level,
+    };
+    // Patch (unlike pre-patch) of a pre-release only drops the pre-release, also when a minor
+    // bump was lowered to a patch bump
+    let finalize = matches!(
+        strategy,
+        Strategy::Patch(_) | Strategy::Minor(_) | Strategy::Auto(_)
+    ) && level == Some(BumpLevel::Patch)
+        && !next.pre.is_empty();
+
+    // Set new major/minor/patch versions, a version from the branch is not released yet
+    match level {
+        _ if context.base.is_some() || requested.is_some() || finalize => {}
+        Some(BumpLevel::Major) => {
             next.major += settings.bump.increment;
             next.minor = 0;
             next.patch = 0;
         }
-        Strategy::Minor(_) | Strategy::PreMinor(_) => {
+        Some(BumpLevel::Minor) => {
             next.minor += settings.bump.increment;
             next.patch = 0;
         }
-        Strategy::Patch(_) => {
-            if next.pre.is_empty() {
-                next.patch += settings.bump.increment;
-            }
-        }
-        Strategy::PrePatch(_) => {
+        Some(BumpLevel::Patch) => {
             next.patch += settings.bump.increment;
         }
-        Strategy::Prerelease(_) => {}
-        Strategy::Dev(_) => {}
+        None => {}
     }
 
     let prerelease_identifier = match &settings.prerelease.identifier {
@@ -237,11 +545,26 @@ pub fn next_version(repo: &Repository, strategy: &Strategy, settings: &Settings)
     let template_variables = TemplateVariables {
         pre: next.pre.as_str().to_string(),
         inc,
-        hash: short_hash,
-        distance: commit_count,
+        hash: context.short_sha().to_string(),
+        distance: context.distance,
         identifier: prerelease_identifier.clone(),
         date_time,
-        branch: branch,
+        branch,
+        pr: context
+            .ci
+            .as_ref()
+            .and_then(|ci| ci.pr.clone())
+            .unwrap_or_default(),
+        build_number: context
+            .ci
+            .as_ref()
+            .and_then(|ci| ci.build_number.clone())
+            .unwrap_or_default(),
+        ci: context
+            .ci
+            .as_ref()
+            .map(|ci| ci.provider.clone())
+            .unwrap_or_default(),
     };
     let pre = handle_prerelease(&settings.prerelease.template, &template_variables);
     let build = handle_build_metadata(&settings.build.template, &template_variables);
@@ -259,21 +582,175 @@ fn handle_build_metadata(template: &str, variables: &TemplateVariables) -> Build
     BuildMetadata::new(variables.inject(template).as_str()).unwrap()
 }
 
-pub fn current_version(repo: &Repository, filter: &Regex) -> Version {
-    match find_latest_semver(repo, filter) {
-        Ok(Some(v)) => v,
-        _ => Version::new(0, 0, 0),
+fn current_version(latest: Option<&LatestTag>) -> Version {
+    match latest {
+        Some(latest) => latest.tag.version.clone(),
+        None => Version::new(0, 0, 0),
     }
 }
 
+/// Looks up the latest tagged version and the facts about the evaluated commit, including the
+/// commits since the tag. Errors are reported and exit
+pub fn latest_version<'r>(
+    settings: &Settings,
+    repo: &'r Repository,
+) -> (Version, VersionContext, CommitRange<'r>) {
+    let target = match resolve_revision(repo, settings.rev.as_deref()) {
+        Ok(target) => target,
+        Err(e) => {
+            eprintln!("Issue resolving revision: {}!", e.message());
+            std::process::exit(1);
+        }
+    };
+    let latest = match find_latest_semver(repo, &settings.filter, target) {
+        Ok(latest) => latest,
+        Err(e) => {
+            eprintln!("Issue selecting latest tag: {}!", e.message());
+            std::process::exit(1);
+        }
+    };
+    if let Some(latest) = &latest {
+        for conflict in &latest.conflicts {
+            eprintln!(
+                "Warning: tags \"{}\" and \"{}\" refer to version {} but point to different commits, using \"{}\"!",
+                latest.tag.name, conflict.name, latest.tag.version, latest.tag.name
+            );
+        }
+    }
+    let tag_commit = latest.as_ref().map(|latest| latest.tag.commit);
+    let range = commits_since(repo, &settings.commits, tag_commit, target).unwrap_or_default();
+    let context = version_context(repo, settings, target, latest.as_ref(), &range);
+    // Crates released before adopting tags continue from their committed manifest version
+    let version = match (&latest, &settings.commits.component) {
+        (None, Some(component)) => component_manifest_version(repo, component, target),
+        _ => None,
+    };
+    let version = version.unwrap_or_else(|| current_version(latest.as_ref()));
+    (version, context, range)
+}
+
+/// Package version in the Cargo manifest of the component at the target commit. The manifest is
+/// looked up in the first component path unless configured
+fn component_manifest_version(
+    repo: &Repository,
+    component: &ComponentSettings,
+    target: Option<Oid>,
+) -> Option<Version> {
+    let manifest = component.manifest.clone().or_else(|| {
+        component
+            .paths
+            .first()
+            .map(|path| path.join(MANIFEST_FILE_NAME))
+    })?;
+    let tree = get_target_commit(repo, target).ok()?.tree().ok()?;
+    let read = |path: &Path| -> Option<String> {
+        // Tree entries are addressed without `.` components
+        let path: PathBuf = path
+            .components()
+            .filter(|part| !matches!(part, Component::CurDir))
+            .collect();
+        let blob = tree
+            .get_path(&path)
+            .ok()?
+            .to_object(repo)
+            .ok()?
+            .peel_to_blob()
+            .ok()?;
+        String::from_utf8(blob.content().to_vec()).ok()
+    };
+    manifest_version(&manifest, read)
+}
+
+/// Determines the version the next release is based on along with the release requested by the
+/// commits since the latest tag
+pub fn compute_next(settings: &Settings, repo: &Repository) -> (Version, VersionContext) {
+    let (mut latest, mut context, range) = latest_version(settings, repo);
+    (context.release_as, context.bump) = required_release(&range.commits, &settings.commits);
+
+    // Branch version is used until a tag of the same or a later release exists
+    if let Some(base) = settings.branch_version() {
+        if (latest.major, latest.minor, latest.patch) < (base.major, base.minor, base.patch) {
+            latest = base.clone();
+            context.base = Some(base);
+        }
+    }
+    (latest, context)
+}
+
+/// Inserts GitVersion-compatible variables into the JSON output map
+fn insert_gitversion_fields(
+    map: &mut serde_json::Map<String, Value>,
+    version: &Version,
+    context: &VersionContext,
+) {
+    let major_minor_patch = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    let sem_ver = if version.pre.is_empty() {
+        major_minor_patch.clone()
+    } else {
+        format!("{}-{}", major_minor_patch, version.pre)
+    };
+    let pre_release_number = version
+        .pre
+        .as_str()
+        .rsplit('.')
+        .next()
+        .and_then(|s| s.parse::<u64>().ok());
+
+    let mut informational: Vec<String> = Vec::new();
+    if !version.build.is_empty() {
+        informational.push(version.build.to_string());
+    }
+    if !context.branch.is_empty() {
+        informational.push(format!("Branch.{}", context.branch));
+    }
+    if !context.sha.is_empty() {
+        informational.push(format!("Sha.{}", context.sha));
+    }
+    let informational_version = if informational.is_empty() {
+        sem_ver.clone()
+    } else {
+        format!("{}+{}", sem_ver, informational.join("."))
+    };
+
+    map.insert("SemVer".to_string(), json!(sem_ver));
+    map.insert("FullSemVer".to_string(), json!(version.to_string()));
+    map.insert("MajorMinorPatch".to_string(), json!(major_minor_patch));
+    map.insert("PreReleaseTag".to_string(), json!(version.pre.as_str()));
+    map.insert(
+        "PreReleaseLabel".to_string(),
+        json!(extract_prerelease_identifier(&version.pre).unwrap_or_default()),
+    );
+    map.insert("PreReleaseNumber".to_string(), json!(pre_release_number));
+    map.insert("BuildMetaData".to_string(), json!(version.build.as_str()));
+    map.insert(
+        "CommitsSinceVersionSource".to_string(),
+        json!(context.distance),
+    );
+    map.insert("Sha".to_string(), json!(context.sha));
+    map.insert("ShortSha".to_string(), json!(context.short_sha()));
+    map.insert("BranchName".to_string(), json!(context.branch));
+    map.insert(
+        "CommitDate".to_string(),
+        json!(context
+            .commit_date
+            .map(|d| d.format("%Y-%m-%d").to_string())),
+    );
+    map.insert(
+        "InformationalVersion".to_string(),
+        json!(informational_version),
+    );
+}
+
 pub fn format_version(
     field: &Option<Field>,
     version: &Version,
-    output_format: &Format,
-    output_template: &str,
+    context: &VersionContext,
+    output: &OutputSettings,
 ) {
-    let full_version = output_template.replace("{version}", version.to_string().as_str());
-    match output_format {
+    let full_version = output
+        .template
+        .replace("{version}", version.to_string().as_str());
+    match output.format {
         Format::Plain => match field {
             None => {
                 println!("{}", full_version);
@@ -305,6 +782,24 @@ pub fn format_version(
                         map.insert("build".to_string(), json!(version.build.as_str()));
                     }
                     map.insert("full".to_string(), json!(full_version));
+                    if context.shallow {
+                        map.insert("shallow".to_string(), json!(true));
+                    }
+                    if !context.conflicts.is_empty() {
+                        map.insert("conflicts".to_string(), json!(context.conflicts));
+                    }
+                    if let FieldSet::All = output.fields {
+                        if let Some(tag) = &context.tag {
+                            map.insert("tag".to_string(), json!(tag));
+                        }
+                        if let Some(order) = &context.order {
+                            let order = order.to_possible_value().unwrap();
+                            map.insert("order".to_string(), json!(order.get_name()));
+                        }
+                        map.insert("distance".to_string(), json!(context.distance));
+                        map.insert("excluded_commits".to_string(), json!(context.excluded));
+                        insert_gitversion_fields(&mut map, version, context);
+                    }
                     Value::Object(map)
                 }
             };
@@ -0,0 +1,303 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{
+    add_all, add_commit, add_tag, checkout_new_branch, create_file, initialize_repository,
+};
+
+static CONFIG: &str = r#"
+[[branches]]
+pattern = "^release/"
+strategy = "pre-patch"
+prerelease.identifier = "rc"
+
+[[branches]]
+pattern = "^feature/"
+prerelease.template = "{identifier}.{branch}.{distance}"
+build_metadata.template = ""
+"#;
+
+#[test]
+fn test_branch_rule_release() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    checkout_new_branch(&repo, "release/1.2");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.4-rc.1\n"));
+}
+
+#[test]
+fn test_branch_rule_feature() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    checkout_new_branch(&repo, "feature/login");
+    add_commit(&repo, "Add login");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3-dev.feature-login.1\n"));
+}
+
+#[test]
+fn test_branch_rule_override_detached_head() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", CONFIG);
+    add_all(&repo);
+    let commit = add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    repo.set_head_detached(commit.id()).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--branch")
+        .arg("release/1.2")
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.4-rc.1\n"));
+}
+
+#[test]
+fn test_branch_rule_cli_wins() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    checkout_new_branch(&repo, "release/1.2");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("pre-minor")
+        .arg("beta")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.3.0-beta.1\n"));
+}
+
+static BRANCH_NAME_CONFIG: &str = r#"
+[branch]
+strip_prefixes = ["feature/", "bugfix/"]
+case = "preserve"
+max_length = 20
+
+[branch.replacements]
+"JIRA-" = "J"
+"#;
+
+#[test]
+fn test_branch_name_normalization() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", BRANCH_NAME_CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    checkout_new_branch(&repo, "bugfix/JIRA-42_Fix");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("dev")
+        .arg("--prerelease-template")
+        .arg("{branch}")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3-J42-Fix\n"));
+}
+
+#[test]
+fn test_branch_name_truncated_with_hash() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", BRANCH_NAME_CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+
+    let branch = "feature/JIRA-1234-some-very-long-description";
+    let hash = git2::Oid::hash_object(git2::ObjectType::Blob, branch.as_bytes()).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--branch")
+        .arg(branch)
+        .arg("next")
+        .arg("dev")
+        .arg("--prerelease-template")
+        .arg("{branch}")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!(
+            "1.2.3-J1234-some-v-h{}\n",
+            &hash.to_string()[..6]
+        )));
+}
+
+#[test]
+fn test_branch_name_shorter_than_hash() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", "branch.max_length = 4");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+
+    let branch = "feature/long-name";
+    let hash = git2::Oid::hash_object(git2::ObjectType::Blob, branch.as_bytes()).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--branch")
+        .arg(branch)
+        .arg("next")
+        .arg("dev")
+        .arg("--prerelease-template")
+        .arg("{branch}")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!(
+            "1.2.3-h{}\n",
+            &hash.to_string()[..3]
+        )));
+}
+
+static BRANCH_VERSION_CONFIG: &str = r#"
+version.source = "branch"
+
+[[branches]]
+pattern = "^release/"
+strategy = "prerelease"
+prerelease.identifier = "rc"
+"#;
+
+#[test]
+fn test_branch_version_without_tags() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", BRANCH_VERSION_CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.3.0");
+    add_tag(&repo, "v2.0.0");
+    checkout_new_branch(&repo, "release/1.4");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.4.0-rc.1\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.0.0\n"));
+}
+
+#[test]
+fn test_branch_version_with_tag_on_line() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", BRANCH_VERSION_CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    checkout_new_branch(&repo, "release/1.4");
+    add_tag(&repo, "v1.4.0-rc.1");
+    add_commit(&repo, "Fix");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.4.0-rc.2\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.4.0-rc.1\n"));
+}
+
+#[test]
+fn test_branch_version_pattern_is_anchored() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", BRANCH_VERSION_CONFIG);
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.3.0");
+    add_tag(&repo, "v2.0.0");
+    checkout_new_branch(&repo, "backport/release/1.4");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+}
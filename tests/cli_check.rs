@@ -0,0 +1,229 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository};
+
+#[test]
+fn test_check_release_needed() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "fix: repair");
+    add_commit(&repo, "feat(cli): add option");
+    add_commit(&repo, "docs: explain option");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("minor\n"));
+}
+
+#[test]
+fn test_check_breaking_change() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "refactor!: drop legacy API");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("-f")
+        .arg("json")
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"bump\": \"major\""));
+}
+
+#[test]
+fn test_check_only_ignored_types() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "docs: update readme");
+    add_commit(&repo, "chore: update dependencies");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .assert()
+        .code(3)
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_check_respects_commit_filters() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        "[commits]\nexclude = ['^fix\\(release\\)']\n",
+    );
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "fix(release): bump version");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_check_no_commits() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "feat: initial");
+    add_tag(&repo, "v1.2.3");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn test_check_revert_cancels_commit() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "fix: repair");
+    let feature = add_commit(&repo, "feat: add option");
+    add_commit(
+        &repo,
+        &format!(
+            "Revert \"feat: add option\"\n\nThis reverts commit {}.\n",
+            feature.id()
+        ),
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("patch\n"));
+}
+
+#[test]
+fn test_check_revert_of_revert() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    let feature = add_commit(&repo, "feat: add option");
+    let revert = add_commit(
+        &repo,
+        &format!(
+            "Revert \"feat: add option\"\n\nThis reverts commit {}.\n",
+            feature.id()
+        ),
+    );
+    add_commit(
+        &repo,
+        &format!(
+            "Revert \"Revert \"feat: add option\"\"\n\nThis reverts commit {}.\n",
+            revert.id()
+        ),
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("minor\n"));
+}
+
+#[test]
+fn test_check_squash_merge_body() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(
+        &repo,
+        "Add export (#12)\n\n* feat(export): add csv export\n\n* fix: typo in help\n",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .assert()
+        .code(3);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env("DOXXER__COMMITS__SQUASH", "body")
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("minor\n"));
+}
+
+#[test]
+fn test_check_release_as_trailer() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "docs: prepare release\n\nRelease-As: 2.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("-f")
+        .arg("json")
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"release\": true"))
+        .stdout(predicate::str::contains("\"release_as\": \"2.0.0\""));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_commit, add_tag, initialize_repository};
+
+static CI_VARIABLES: [&str; 6] = [
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "JENKINS_URL",
+    "TF_BUILD",
+    "BUILDKITE",
+    "CIRCLECI",
+];
+
+fn doxxer() -> Command {
+    let mut command = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    for variable in CI_VARIABLES {
+        command.env_remove(variable);
+    }
+    command
+}
+
+#[test]
+fn test_ci_github_pull_request() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "Second commit");
+    let head = repo.head().unwrap().target().unwrap();
+    repo.set_head_detached(head).unwrap();
+
+    doxxer()
+        .current_dir(td)
+        .env("GITHUB_ACTIONS", "true")
+        .env("GITHUB_HEAD_REF", "feature/login")
+        .env("GITHUB_REF", "refs/pull/42/merge")
+        .env("GITHUB_RUN_NUMBER", "7")
+        .arg("next")
+        .arg("dev")
+        .arg("--prerelease-template")
+        .arg("{ci}.{branch}.pr{pr}.{build_number}")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0-github.feature-login.pr42.7\n"));
+}
+
+#[test]
+fn test_ci_gitlab_branch() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    let head = repo.head().unwrap().target().unwrap();
+    repo.set_head_detached(head).unwrap();
+
+    doxxer()
+        .current_dir(td)
+        .env("GITLAB_CI", "true")
+        .env("CI_COMMIT_BRANCH", "main")
+        .env("CI_PIPELINE_IID", "12")
+        .arg("next")
+        .arg("dev")
+        .arg("--prerelease-template")
+        .arg("{branch}.{build_number}")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0-main.12\n"));
+}
+
+#[test]
+fn test_ci_local_branch_takes_precedence() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+
+    doxxer()
+        .current_dir(td)
+        .env("BUILDKITE", "true")
+        .env("BUILDKITE_BRANCH", "other")
+        .env("BUILDKITE_PULL_REQUEST", "false")
+        .arg("next")
+        .arg("dev")
+        .arg("--prerelease-template")
+        .arg("{branch}{pr}")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0-master\n"));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{
+    add_all, add_commit, add_detached_commit, add_merge_commit, add_tag, create_file,
+    initialize_repository,
+};
+
+#[test]
+fn test_commits_exclude() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        "[commits]\nexclude = ['^chore\\(release\\)', '^Merge']\n",
+    );
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "feat: add feature");
+    add_commit(&repo, "chore(release): bump version");
+    add_commit(&repo, "fix: repair feature");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("dev")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0-dev.2\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("json")
+        .arg("--verbose-json")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"distance\": 2"))
+        .stdout(predicate::str::contains("\"excluded_commits\": 1"));
+}
+
+#[test]
+fn test_commits_exclude_env() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "Merge branch 'feature'");
+    add_commit(&repo, "chore(release): bump version");
+    add_commit(&repo, "fix: repair feature");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env("DOXXER__COMMITS__EXCLUDE", "^chore,^Merge")
+        .arg("next")
+        .arg("dev")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0-dev.1\n"));
+}
+
+#[test]
+fn test_commits_first_parent() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    let initial = add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    let feature = add_detached_commit(&repo, &initial, "feat: first step");
+    let feature = add_detached_commit(&repo, &feature, "feat: second step");
+    add_commit(&repo, "fix: on main");
+    add_merge_commit(&repo, &feature, "Merge branch 'feature'");
+
+    for (first_parent, expected) in [("false", "1.0.0-dev.4\n"), ("true", "1.0.0-dev.2\n")] {
+        Command::cargo_bin(env!("CARGO_PKG_NAME"))
+            .unwrap()
+            .current_dir(td)
+            .env("DOXXER__COMMITS__FIRST_PARENT", first_parent)
+            .arg("next")
+            .arg("dev")
+            .arg("--build-metadata-template")
+            .arg("")
+            .assert()
+            .success()
+            .stdout(predicate::str::diff(expected));
+    }
+}
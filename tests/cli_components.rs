@@ -0,0 +1,156 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository};
+
+static CONFIG: &str = r#"
+[components.api]
+paths = ["api"]
+scopes = ["api", "shared"]
+
+[components.web]
+paths = ["web"]
+"#;
+
+fn monorepo(td: &std::path::Path) -> git2::Repository {
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", CONFIG);
+    std::fs::create_dir_all(td.join("api")).unwrap();
+    std::fs::create_dir_all(td.join("web")).unwrap();
+    create_file(&td.join("api"), "lib.rs", "");
+    create_file(&td.join("web"), "index.html", "");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "api-v1.0.0");
+    add_tag(&repo, "web-v2.0.0");
+    repo
+}
+
+#[test]
+fn test_component_scope_and_paths() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = monorepo(td);
+    // Scoped to a shared library used by the api component, touches no api path
+    add_commit(&repo, "feat(shared): add helper");
+    create_file(&td.join("web"), "index.html", "changed");
+    add_all(&repo);
+    add_commit(&repo, "fix: repair page");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("api")
+        .arg("next")
+        .arg("auto")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.1.0\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("web")
+        .arg("next")
+        .arg("auto")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.1\n"));
+}
+
+#[test]
+fn test_component_distance() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = monorepo(td);
+    add_commit(&repo, "fix(api): first");
+    add_commit(&repo, "fix(web): second");
+    add_commit(&repo, "fix(api): third");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env("DOXXER__COMPONENT", "api")
+        .arg("next")
+        .arg("dev")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0-dev.2\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env("DOXXER__COMPONENT", "api")
+        .arg("--verbose-json")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"distance\": 2"))
+        .stdout(predicate::str::contains("\"excluded_commits\": 0"));
+}
+
+#[test]
+fn test_component_without_config_uses_name_as_scope() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "cli-v0.3.0");
+    add_tag(&repo, "v9.9.9");
+    add_commit(&repo, "feat(core): unrelated");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("cli")
+        .arg("check")
+        .assert()
+        .code(3);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("cli")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.3.0\n"));
+}
+
+#[test]
+fn test_component_cli_tag_template() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = monorepo(td);
+    add_tag(&repo, "api/3.0.0");
+    add_tag(&repo, "web/4.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("api")
+        .arg("--tag-template")
+        .arg("{component}/{version}")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("3.0.0\n"));
+}
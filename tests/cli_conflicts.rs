@@ -0,0 +1,111 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_annotated_tag, add_commit, add_tag, create_file, initialize_repository};
+
+#[test]
+fn test_conflict_prefer_annotated() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "1.2.0");
+    add_commit(&repo, "Second commit");
+    add_annotated_tag(&repo, "v1.2.0", "Release 1.2.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("json")
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""pre": "dev.0""#))
+        .stdout(predicate::str::contains(r#""conflicts": ["#))
+        .stderr(predicate::str::contains(
+            r#"tags "v1.2.0" and "1.2.0" refer to version 1.2.0"#,
+        ));
+}
+
+#[test]
+fn test_conflict_same_commit_is_not_reported() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "1.2.0");
+    add_tag(&repo, "v1.2.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.0\n"))
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_conflict_error() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "1.2.0");
+    add_commit(&repo, "Second commit");
+    add_tag(&repo, "v1.2.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--tag-conflict")
+        .arg("error")
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "tags for version 1.2.0 point to different commits",
+        ));
+}
+
+#[test]
+fn test_conflict_error_topology() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "1.2.0");
+    add_commit(&repo, "Second commit");
+    add_tag(&repo, "v1.2.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--tag-order")
+        .arg("topology")
+        .arg("--tag-conflict")
+        .arg("error")
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "tags for version 1.2.0 point to different commits",
+        ));
+}
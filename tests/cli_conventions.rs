@@ -0,0 +1,179 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository};
+
+#[test]
+fn test_next_auto_conventional() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "fix: repair");
+    add_commit(&repo, "feat: add option");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("auto")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.3.0\n"));
+}
+
+#[test]
+fn test_next_auto_defaults_to_patch() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "docs: explain option");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("auto")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.4\n"));
+}
+
+#[test]
+fn test_next_auto_zero_major_mode() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", "zero_major_mode = true\n");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v0.4.2");
+    add_commit(&repo, "feat!: drop legacy API");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("auto")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.5.0\n"));
+}
+
+#[test]
+fn test_gitmoji_convention() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", "[commits]\nconvention = \"gitmoji\"\n");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "🐛 Fix crash on empty input");
+    add_commit(&repo, ":sparkles: Add export");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("minor\n"));
+
+    add_commit(&repo, "💥 Remove deprecated API");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("auto")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+}
+
+#[test]
+fn test_regex_convention() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        r#"
+[commits]
+convention = "regex"
+
+[commits.patterns]
+minor = ['^\[feature\]']
+patch = ['^\[bugfix\]', '^\[security\]']
+"#,
+    );
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "[security] Update dependency");
+    add_commit(&repo, "feat: ignored by this convention");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("patch\n"));
+}
+
+#[test]
+fn test_regex_convention_ignores_body() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        r#"
+[commits]
+convention = "regex"
+
+[commits.patterns]
+major = ['\[major\]']
+patch = ['^Update']
+"#,
+    );
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "Update docs\n\nMention the [major] pattern");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .arg("--bump")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("patch\n"));
+}
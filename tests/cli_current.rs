@@ -5,7 +5,7 @@ use common::add_tag;
 use predicates::prelude::*;
 use std::process::Command;
 
-use crate::common::{add_all, add_commit, create_file, initialize_repository};
+use crate::common::{add_all, add_commit, add_detached_commit, create_file, initialize_repository};
 
 #[test]
 fn test_current_no_git_repo() {
@@ -82,3 +82,375 @@ fn test_current_repo_975_release() {
         .success()
         .stdout(predicate::str::contains("0.0.0"));
 }
+
+#[test]
+fn test_current_verbose_json() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "1.2.3-beta.4");
+    add_commit(&repo, "Second commit");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("json")
+        .arg("--verbose-json")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""SemVer": "1.2.3-beta.4""#))
+        .stdout(predicate::str::contains(r#""MajorMinorPatch": "1.2.3""#))
+        .stdout(predicate::str::contains(r#""PreReleaseLabel": "beta""#))
+        .stdout(predicate::str::contains(r#""PreReleaseNumber": 4"#))
+        .stdout(predicate::str::contains(
+            r#""CommitsSinceVersionSource": 1"#,
+        ));
+}
+
+#[test]
+fn test_current_verbose_json_implies_json() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "1.2.3");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--verbose-json")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""SemVer": "1.2.3""#));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("plain")
+        .arg("--verbose-json")
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "--verbose-json requires JSON output",
+        ));
+}
+
+#[test]
+fn test_current_rev() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "Second commit");
+    add_tag(&repo, "v2.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--rev")
+        .arg("HEAD~1")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0\n"));
+}
+
+#[test]
+fn test_current_rev_head() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "Second commit");
+    add_tag(&repo, "v2.0.0");
+    create_file(td, "doxxer.toml", "rev = \"v1.0.0\"");
+
+    for args in [vec!["current"], vec!["--rev", "HEAD", "current"]] {
+        Command::cargo_bin(env!("CARGO_PKG_NAME"))
+            .unwrap()
+            .current_dir(td)
+            .args(args)
+            .assert()
+            .success()
+            .stdout(predicate::str::diff("2.0.0\n"));
+    }
+}
+
+#[test]
+fn test_current_tag_on_unmerged_branch() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    let initial = add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    let release = add_detached_commit(&repo, &initial, "Release commit");
+    repo.tag_lightweight("v1.1.0", release.as_object(), false)
+        .unwrap();
+    add_commit(&repo, "Second commit");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.1.0\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .args(["next", "minor"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.0\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .args(["--rev", "HEAD", "current"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0\n"));
+}
+
+#[test]
+fn test_current_rev_bare_repo() {
+    let td = tempfile::tempdir().unwrap();
+    let source = td.path().join("source");
+    let bare = td.path().join("bare.git");
+
+    let repo = initialize_repository(&source);
+    create_file(&source, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "Second commit");
+    add_tag(&repo, "v1.1.0");
+    git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(source.to_str().unwrap(), &bare)
+        .unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&bare)
+        .arg("--rev")
+        .arg("v1.0.0")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0\n"));
+}
+
+#[test]
+fn test_current_version_requirement_without_prereleases() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    for tag in ["v1.0.0", "v1.1.0", "v1.2.0-rc.1", "v2.0.0"] {
+        add_commit(&repo, tag);
+        add_tag(&repo, tag);
+    }
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--version-filter")
+        .arg(">=1.0, <2")
+        .arg("--prerelease-filter")
+        .arg("exclude")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.1.0\n"));
+}
+
+#[test]
+fn test_current_exclude_env() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    for tag in ["v1.0.0", "v1.2.0-rc.1", "v2.0.0", "nightly-3.0.0"] {
+        add_commit(&repo, tag);
+        add_tag(&repo, tag);
+    }
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env("DOXXER__FILTER__EXCLUDE", "^v2,^nightly")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.0-rc.1\n"));
+}
+
+#[test]
+fn test_current_invalid_exclude_pattern() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--exclude-tag")
+        .arg("^v(")
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Invalid tag exclude pattern \"^v(\"",
+        ));
+}
+
+#[test]
+fn test_current_version_capture_group() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "svc2.0.0-1.4.3");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--tag-filter")
+        .arg(r"^svc\d+\.\d+\.\d+-(?P<version>.+)$")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.4.3\n"));
+}
+
+#[test]
+fn test_current_tag_template() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "api-v1-2.3.4");
+    add_tag(&repo, "web-v3.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--tag-filter")
+        .arg("^api-")
+        .arg("--tag-template")
+        .arg("{component}-v1-{version}")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.3.4\n"));
+}
+
+#[test]
+fn test_current_order_topology() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v2.0.0");
+    add_commit(&repo, "Backport fix");
+    add_tag(&repo, "v1.5.1");
+    add_commit(&repo, "Another commit");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--tag-order")
+        .arg("topology")
+        .arg("--format")
+        .arg("json")
+        .arg("--verbose-json")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""full": "1.5.1""#))
+        .stdout(predicate::str::contains(r#""order": "topology""#))
+        .stdout(predicate::str::contains(r#""tag": "v1.5.1""#));
+}
+
+#[test]
+fn test_current_order_tagger_date() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    let tag_at = |name: &str, seconds: i64| {
+        let obj = repo.revparse_single("HEAD").unwrap();
+        let tagger = git2::Signature::new(
+            "Test User",
+            "test@example.com",
+            &git2::Time::new(seconds, 0),
+        )
+        .unwrap();
+        repo.tag(name, &obj, &tagger, "Release", false).unwrap();
+    };
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    tag_at("v1.5.1", 1_700_000_100);
+    add_commit(&repo, "Second commit");
+    tag_at("v2.0.0", 1_700_000_000);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--tag-order")
+        .arg("tagger-date")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.5.1\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{fs, process::Command};
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository};
+
+fn create_tagged_repository(path: &std::path::Path) -> git2::Repository {
+    let repo = initialize_repository(path);
+    create_file(path, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    repo
+}
+
+#[test]
+fn test_discovery_subdirectory() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    create_tagged_repository(td);
+    let subdirectory = td.join("crates").join("core");
+    fs::create_dir_all(&subdirectory).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&subdirectory)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3\n"));
+}
+
+#[test]
+fn test_discovery_config_in_repository_root() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    create_tagged_repository(td);
+    create_file(td, "doxxer.toml", "output.template = \"v{version}\"");
+    let subdirectory = td.join("crates");
+    fs::create_dir_all(&subdirectory).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&subdirectory)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("v1.2.3\n"));
+}
+
+#[test]
+fn test_discovery_bare_repository() {
+    let td = tempfile::tempdir().unwrap();
+    let source = td.path().join("source");
+    let bare = td.path().join("bare.git");
+    create_tagged_repository(&source);
+    git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(source.to_str().unwrap(), &bare)
+        .unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&bare)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3\n"));
+}
+
+#[test]
+fn test_discovery_linked_worktree() {
+    let td = tempfile::tempdir().unwrap();
+    let main = td.path().join("main");
+    let linked = td.path().join("linked");
+    let repo = create_tagged_repository(&main);
+    repo.worktree("linked", &linked, None).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&linked)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3\n"));
+}
+
+#[test]
+fn test_discovery_git_dir() {
+    let td = tempfile::tempdir().unwrap();
+    let elsewhere = tempfile::tempdir().unwrap();
+    create_tagged_repository(td.path());
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(elsewhere.path())
+        .env("GIT_DIR", td.path().join(".git"))
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3\n"));
+}
+
+#[test]
+fn test_discovery_ceiling_directories() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    create_tagged_repository(td);
+    let subdirectory = td.join("sub");
+    fs::create_dir_all(&subdirectory).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&subdirectory)
+        .env("GIT_CEILING_DIRECTORIES", td)
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Issue opening repository"));
+}
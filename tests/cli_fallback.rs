@@ -0,0 +1,86 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository};
+
+#[test]
+fn test_fallback_pretend_version() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env("DOXXER_PRETEND_VERSION", "2.3.4-rc.1")
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.3.4-rc.1\n"));
+}
+
+#[test]
+fn test_fallback_archival_file() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    create_file(
+        td,
+        ".git_archival.txt",
+        "node: 3f2b1c0d9e8a7b6c5d4e3f2a1b0c9d8e7f6a5b4c\ndescribe-name: v1.2.3-4-g3f2b1c0",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.2.3-dev.4+3f2b1c0\n"));
+}
+
+#[test]
+fn test_fallback_archival_file_not_substituted() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    create_file(
+        td,
+        ".git_archival.txt",
+        "node: $Format:%H$\ndescribe-name: $Format:%(describe:tags=true)$",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Issue opening repository"));
+}
+
+#[test]
+fn test_fallback_manifest() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    create_file(td, "doxxer.toml", "fallback.manifest = \"Cargo.toml\"");
+    create_file(
+        td,
+        "Cargo.toml",
+        "[package]\nname = \"example\"\nversion = \"0.4.2\"",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.4.2\n"));
+}
@@ -0,0 +1,201 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, create_file, get_short_hash, initialize_repository};
+
+#[test]
+fn test_lint_commit_valid_file() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    initialize_repository(td);
+    create_file(
+        td,
+        "COMMIT_EDITMSG",
+        "feat(cli): add option\n\nLonger description.\n# Please enter the commit message",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("lint-commit")
+        .arg("COMMIT_EDITMSG")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_lint_commit_stdin() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    initialize_repository(td);
+
+    assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("lint-commit")
+        .arg("-")
+        .write_stdin("Update stuff\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "does not follow Conventional Commits",
+        ));
+}
+
+#[test]
+fn test_lint_commit_types_and_scopes() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        "[commits]\ntypes = [\"feat\", \"fix\"]\nscopes = [\"cli\"]\n",
+    );
+
+    assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("lint-commit")
+        .arg("-")
+        .write_stdin("docs: explain\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("type \"docs\" is not allowed"));
+
+    assert_cmd::Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("lint-commit")
+        .arg("-")
+        .write_stdin("fix(core): repair\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("scope \"core\" is not allowed"));
+}
+
+#[test]
+fn test_lint_range() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_commit(&repo, "feat: valid");
+    let invalid = add_commit(&repo, "fixed things");
+    add_commit(&repo, "fix: valid");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("lint-range")
+        .arg("HEAD~3..HEAD")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "{} fixed things",
+            get_short_hash(&invalid)
+        )))
+        .stderr(predicate::str::contains(
+            "Found 1 invalid commit message(s)!",
+        ));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("lint-range")
+        .arg("HEAD~1..HEAD")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_hooks_install() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "content");
+    add_all(&repo);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("hooks")
+        .arg("install")
+        .assert()
+        .success();
+
+    let hook = std::fs::read_to_string(repo.path().join("hooks").join("commit-msg")).unwrap();
+    assert!(hook.contains("doxxer lint-commit \"$1\""));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("hooks")
+        .arg("install")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("hooks")
+        .arg("install")
+        .arg("--force")
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_hooks_install_hooks_path() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    repo.config()
+        .unwrap()
+        .set_str("core.hooksPath", ".githooks")
+        .unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("hooks")
+        .arg("install")
+        .assert()
+        .success();
+
+    assert!(td.join(".githooks").join("commit-msg").is_file());
+}
+
+#[test]
+fn test_hooks_install_linked_worktree() {
+    let td = tempfile::tempdir().unwrap();
+    let worktrees = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let worktree = worktrees.path().join("feature");
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    repo.worktree("feature", &worktree, None).unwrap();
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(&worktree)
+        .arg("hooks")
+        .arg("install")
+        .assert()
+        .success();
+
+    assert!(repo.path().join("hooks").join("commit-msg").is_file());
+}
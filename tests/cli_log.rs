@@ -0,0 +1,70 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_commit, add_tag, get_short_hash, initialize_repository};
+
+#[test]
+fn test_log_since_latest_tag() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "feat: before release");
+    add_tag(&repo, "v1.2.3");
+    let fix = add_commit(&repo, "fix(parser): handle empty input");
+    let feat = add_commit(&repo, "feat(api)!: remove endpoint");
+    add_commit(&repo, "Update readme");
+
+    let output = Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("log")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(lines.len(), 4);
+    assert!(lines[0].starts_with("COMMIT"));
+    assert!(lines[1].ends_with("Update readme"));
+    assert!(lines[2].starts_with(&get_short_hash(&feat)));
+    assert!(lines[2].contains("feat  api     yes"));
+    assert!(lines[3].starts_with(&get_short_hash(&fix)));
+    assert!(lines[3].contains("Test User"));
+    assert!(!stdout.contains("before release"));
+}
+
+#[test]
+fn test_log_json_range() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.0.0");
+    add_commit(&repo, "fix: first\n\nBREAKING CHANGE: drops support");
+    add_tag(&repo, "v1.1.0");
+    add_commit(&repo, "feat: second");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("json")
+        .arg("log")
+        .arg("--from")
+        .arg("v1.0.0")
+        .arg("--to")
+        .arg("v1.1.0")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"subject\": \"fix: first\""))
+        .stdout(predicate::str::contains("\"type\": \"fix\""))
+        .stdout(predicate::str::contains("\"scope\": null"))
+        .stdout(predicate::str::contains("\"breaking\": true"))
+        .stdout(predicate::str::contains("second").not());
+}
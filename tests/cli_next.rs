@@ -24,7 +24,7 @@ fn test_next_patch_repo_975() {
         .assert()
         .success()
         .stdout(predicate::str::contains(format!(
-            "9.7.5-dev.1+{}",
+            "9.7.5-dev.0+{}",
             get_short_hash(&commit)
         )));
 }
@@ -176,3 +176,118 @@ fn test_next_minor_override_identifier() {
         .success()
         .stdout(predicate::str::contains("5.1.2-beta.1"));
 }
+
+#[test]
+fn test_next_dev_distance_prefixed_tag() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "Second commit");
+    let commit = add_commit(&repo, "Third commit");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(format!(
+            "1.2.3-dev.2+{}\n",
+            get_short_hash(&commit)
+        )));
+}
+
+#[test]
+fn test_next_zero_major_mode() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", "zero_major_mode = true\n");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v0.4.2");
+
+    for (strategy, expected) in [
+        ("major", "0.5.0\n"),
+        ("minor", "0.4.3\n"),
+        ("patch", "0.4.3\n"),
+    ] {
+        Command::cargo_bin(env!("CARGO_PKG_NAME"))
+            .unwrap()
+            .current_dir(td)
+            .arg("next")
+            .arg(strategy)
+            .arg("--build-metadata-template")
+            .arg("")
+            .assert()
+            .success()
+            .stdout(predicate::str::diff(expected));
+    }
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--allow-major-from-zero")
+        .arg("next")
+        .arg("major")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0\n"));
+}
+
+#[test]
+fn test_next_zero_major_mode_prerelease() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", "zero_major_mode = true\n");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v0.4.2-rc.1");
+
+    for (strategy, expected) in [("minor", "0.4.2\n"), ("patch", "0.4.2\n")] {
+        Command::cargo_bin(env!("CARGO_PKG_NAME"))
+            .unwrap()
+            .current_dir(td)
+            .arg("next")
+            .arg(strategy)
+            .arg("--build-metadata-template")
+            .arg("")
+            .assert()
+            .success()
+            .stdout(predicate::str::diff(expected));
+    }
+}
+
+#[test]
+fn test_next_allow_major_from_zero_keeps_minor_remap() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "doxxer.toml", "zero_major_mode = true\n");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v0.4.2");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--allow-major-from-zero")
+        .arg("next")
+        .arg("minor")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.4.3\n"));
+}
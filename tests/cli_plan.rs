@@ -0,0 +1,165 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository, make_shallow};
+
+#[test]
+fn test_plan_declared_dependencies() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        r#"
+[components.core]
+[components.cli]
+dependencies = ["core"]
+[components.docs]
+"#,
+    );
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "core-v1.0.0");
+    add_tag(&repo, "cli-v2.0.0");
+    add_tag(&repo, "docs-v0.1.0");
+    add_commit(&repo, "fix(core): handle empty input");
+
+    let output = Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("json")
+        .arg("plan")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let plan: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let components = plan["components"].as_array().unwrap();
+
+    assert_eq!(components[0]["name"], "cli");
+    assert_eq!(components[0]["current"], "2.0.0");
+    assert_eq!(components[0]["next"], "2.0.1");
+    assert_eq!(components[0]["next_tag"], "cli-v2.0.1");
+    assert_eq!(components[0]["reason"], "dependency");
+    assert_eq!(components[0]["dependencies"], serde_json::json!(["core"]));
+
+    assert_eq!(components[1]["name"], "core");
+    assert_eq!(components[1]["tag"], "core-v1.0.0");
+    assert_eq!(components[1]["next"], "1.0.1");
+    assert_eq!(components[1]["bump"], "patch");
+    assert_eq!(components[1]["reason"], "commits");
+
+    assert_eq!(components[2]["name"], "docs");
+    assert_eq!(components[2]["next"], serde_json::Value::Null);
+    assert_eq!(components[2]["reason"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_plan_cargo_path_dependencies() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        r#"
+[components.core]
+paths = ["core"]
+[components.app]
+paths = ["app"]
+[components.tool]
+paths = ["tool"]
+"#,
+    );
+    for (name, dependencies) in [
+        ("core", ""),
+        ("app", "core = { path = \"../core\", version = \"1.0.0\" }"),
+        ("tool", "[build-dependencies.app]\npath = \"../app\""),
+    ] {
+        std::fs::create_dir_all(td.join(name)).unwrap();
+        create_file(
+            &td.join(name),
+            "Cargo.toml",
+            &format!(
+                "[package]\nname = \"{}\"\nversion = \"1.0.0\"\n\n[dependencies]\n{}",
+                name, dependencies
+            ),
+        );
+    }
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "core-v1.0.0");
+    add_tag(&repo, "app-v1.0.0");
+    add_tag(&repo, "tool-v1.0.0");
+    create_file(&td.join("core"), "lib.rs", "");
+    add_all(&repo);
+    add_commit(&repo, "feat: add parser");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("plan")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "COMPONENT  CURRENT  NEXT   BUMP   REASON\n\
+             app        1.0.0    1.0.1  patch  dependency\n\
+             core       1.0.0    1.1.0  minor  commits\n\
+             tool       1.0.0    1.0.1  patch  dependency\n",
+        ));
+}
+
+#[test]
+fn test_plan_unknown_dependency() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        "[components.cli]\ndependencies = [\"core\"]\n",
+    );
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("plan")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Component \"cli\" depends on unknown component \"core\"!",
+        ));
+}
+
+#[test]
+fn test_plan_shallow_error() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "doxxer.toml",
+        "shallow = \"error\"\n[components.core]\n",
+    );
+    add_all(&repo);
+    let commit = add_commit(&repo, "feat(core): initial commit");
+    make_shallow(&repo, &commit);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("plan")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("shallow clone"));
+}
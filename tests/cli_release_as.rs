@@ -0,0 +1,117 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_commit, add_tag, get_short_hash, initialize_repository};
+
+#[test]
+fn test_release_as_trailer() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "Rebrand\n\nRelease-As: 2.0.0\n");
+    add_commit(&repo, "Fix typo");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("patch")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("pre-minor")
+        .arg("rc")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0-rc.1\n"));
+}
+
+#[test]
+fn test_release_as_ignored_before_tag() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Rebrand\n\nDoxxer-Version: 2.0.0\n");
+    add_tag(&repo, "v2.0.0");
+    add_commit(&repo, "Fix");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("patch")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.1\n"));
+}
+
+#[test]
+fn test_release_as_conflict() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    let first = add_commit(&repo, "Rebrand\n\nRelease-As: 2.0.0\n");
+    let second = add_commit(&repo, "Rebrand again\n\nDoxxer-Version: 3.0.0\n");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("patch")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(format!(
+            "{} 2.0.0",
+            get_short_hash(&first)
+        )))
+        .stderr(predicate::str::contains(format!(
+            "{} 3.0.0",
+            get_short_hash(&second)
+        )));
+}
+
+#[test]
+fn test_next_set() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "v1.2.3");
+    add_commit(&repo, "Rebrand\n\nRelease-As: 2.0.0\n");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("next")
+        .arg("--set")
+        .arg("3.1.0")
+        .arg("pre-patch")
+        .arg("rc")
+        .arg("--build-metadata-template")
+        .arg("")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("3.1.0-rc.1\n"));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+use common::{add_all, add_commit, create_file, initialize_repository, make_shallow};
+
+#[test]
+fn test_shallow_warn() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    let commit = add_commit(&repo, "Initial commit");
+    make_shallow(&repo, &commit);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--format")
+        .arg("json")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""shallow": true"#))
+        .stderr(predicate::str::contains("shallow clone"));
+}
+
+#[test]
+fn test_shallow_error() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    create_file(td, "doxxer.toml", "shallow = \"error\"");
+    add_all(&repo);
+    let commit = add_commit(&repo, "Initial commit");
+    make_shallow(&repo, &commit);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("current")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("shallow clone"));
+}
+
+#[test]
+fn test_shallow_ignore() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    let commit = add_commit(&repo, "Initial commit");
+    make_shallow(&repo, &commit);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--shallow")
+        .arg("ignore")
+        .arg("current")
+        .assert()
+        .success()
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_shallow_error_check() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    create_file(td, "doxxer.toml", "shallow = \"error\"");
+    add_all(&repo);
+    let commit = add_commit(&repo, "feat: initial commit");
+    make_shallow(&repo, &commit);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("shallow clone"));
+}
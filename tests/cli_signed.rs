@@ -0,0 +1,207 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use git2::{ObjectType, Repository};
+use predicates::prelude::*;
+use std::{fs, path::Path, process::Command};
+
+use common::{add_all, add_annotated_tag, add_commit, add_tag, create_file, initialize_repository};
+
+/// Creates SSH key pair and allowed signers file, returns path to the private key
+fn create_ssh_key(dir: &Path) -> std::path::PathBuf {
+    let key = dir.join("key");
+    let status = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+        .arg(&key)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let public_key = fs::read_to_string(dir.join("key.pub")).unwrap();
+    fs::write(
+        dir.join("allowed_signers"),
+        format!("test@example.com {}", public_key),
+    )
+    .unwrap();
+    key
+}
+
+fn add_ssh_signed_tag(repo: &Repository, key: &Path, tag_name: &str, message: &str) {
+    let head = repo.revparse_single("HEAD").unwrap();
+    let payload = format!(
+        "object {}\ntype commit\ntag {}\ntagger Test User <test@example.com> 1700000000 +0000\n\n{}\n",
+        head.id(),
+        tag_name,
+        message
+    );
+    let payload_file = key.with_file_name(format!("{}.payload", tag_name));
+    fs::write(&payload_file, &payload).unwrap();
+    let status = Command::new("ssh-keygen")
+        .args(["-q", "-Y", "sign", "-n", "git", "-f"])
+        .arg(key)
+        .arg(&payload_file)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let signature = fs::read_to_string(payload_file.with_extension("payload.sig")).unwrap();
+
+    let oid = repo
+        .odb()
+        .unwrap()
+        .write(
+            ObjectType::Tag,
+            format!("{}{}", payload, signature).as_bytes(),
+        )
+        .unwrap();
+    repo.reference(&format!("refs/tags/{}", tag_name), oid, false, "")
+        .unwrap();
+}
+
+#[test]
+fn test_annotated_only() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_annotated_tag(&repo, "v1.0.0", "Release 1.0.0");
+    add_commit(&repo, "Second commit");
+    add_tag(&repo, "v1.1.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--annotated-only")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.0.0\n"));
+}
+
+#[test]
+fn test_signed_only_ssh() {
+    let td = tempfile::tempdir().unwrap();
+    let keys = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let key = create_ssh_key(keys.path());
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_ssh_signed_tag(&repo, &key, "v2.0.0", "Release");
+    add_commit(&repo, "Second commit");
+    add_annotated_tag(&repo, "v2.1.0", "Unsigned release");
+    add_commit(&repo, "Third commit");
+    add_tag(&repo, "v3.0.0");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env(
+            "DOXXER__FILTER__ALLOWED_SIGNERS",
+            keys.path().join("allowed_signers"),
+        )
+        .arg("--signed-only")
+        .arg("ssh")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+}
+
+#[test]
+fn test_signed_only_ssh_topology() {
+    let td = tempfile::tempdir().unwrap();
+    let keys = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let key = create_ssh_key(keys.path());
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_ssh_signed_tag(&repo, &key, "v2.0.0", "Release");
+    add_commit(&repo, "Second commit");
+    add_annotated_tag(&repo, "v2.0.1", "Unsigned release");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env(
+            "DOXXER__FILTER__ALLOWED_SIGNERS",
+            keys.path().join("allowed_signers"),
+        )
+        .arg("--signed-only")
+        .arg("ssh")
+        .arg("--tag-order")
+        .arg("topology")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+}
+
+#[test]
+fn test_signed_only_unknown_signer() {
+    let td = tempfile::tempdir().unwrap();
+    let keys = tempfile::tempdir().unwrap();
+    let other_keys = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let key = create_ssh_key(keys.path());
+    create_ssh_key(other_keys.path());
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_ssh_signed_tag(&repo, &key, "v2.0.0", "Release");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env(
+            "DOXXER__FILTER__ALLOWED_SIGNERS",
+            other_keys.path().join("allowed_signers"),
+        )
+        .arg("--signed-only")
+        .arg("any")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.0.0\n"));
+}
+
+#[test]
+fn test_signed_only_quoted_signature_in_message() {
+    let td = tempfile::tempdir().unwrap();
+    let keys = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let key = create_ssh_key(keys.path());
+
+    let repo = initialize_repository(td);
+    create_file(td, "file.txt", "initial content");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_ssh_signed_tag(
+        &repo,
+        &key,
+        "v2.0.0",
+        "Release\n\n-----BEGIN PGP SIGNATURE-----\nquoted\n-----END PGP SIGNATURE-----",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .env(
+            "DOXXER__FILTER__ALLOWED_SIGNERS",
+            keys.path().join("allowed_signers"),
+        )
+        .arg("--signed-only")
+        .arg("ssh")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.0\n"));
+}
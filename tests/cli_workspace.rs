@@ -0,0 +1,310 @@
+mod common;
+
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{fs, path::Path, process::Command};
+
+use common::{add_all, add_commit, add_tag, create_file, initialize_repository};
+
+static WORKSPACE_MANIFEST: &str = r#"[workspace]
+members = ["crates/*"]
+exclude = ["crates/legacy"]
+
+[workspace.dependencies]
+core = { path = "crates/core", version = "1.0.0" }
+"#;
+
+fn create_crate(td: &Path, name: &str, version: &str, dependencies: &str) {
+    let directory = td.join("crates").join(name);
+    fs::create_dir_all(&directory).unwrap();
+    create_file(
+        &directory,
+        "Cargo.toml",
+        &format!(
+            "[package]\nname = \"{}\"\nversion = \"{}\" # keep\n\n{}",
+            name, version, dependencies
+        ),
+    );
+}
+
+fn workspace(td: &Path) -> git2::Repository {
+    let repo = initialize_repository(td);
+    create_file(td, "Cargo.toml", WORKSPACE_MANIFEST);
+    create_crate(td, "core", "1.0.0", "");
+    create_crate(
+        td,
+        "cli",
+        "2.0.0",
+        "[dependencies]\ncore = { workspace = true }\n",
+    );
+    create_crate(
+        td,
+        "web",
+        "0.3.0",
+        "[dev-dependencies]\ncore = { path = \"../core\", version = \"=1.0.0\" }\n",
+    );
+    create_crate(td, "legacy", "0.1.0", "");
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "core-v1.0.0");
+    add_tag(&repo, "cli-v2.0.0");
+    create_file(&td.join("crates").join("core"), "lib.rs", "");
+    add_all(&repo);
+    add_commit(&repo, "feat: add parser");
+    repo
+}
+
+#[test]
+fn test_workspace_versions() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    workspace(td);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "CRATE  CURRENT  NEXT   BUMP   REASON\n\
+             cli    2.0.0    2.0.1  patch  dependency\n\
+             core   1.0.0    1.1.0  minor  commits\n\
+             web    0.3.0\n",
+        ));
+}
+
+#[test]
+fn test_workspace_write() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    workspace(td);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .arg("--write")
+        .assert()
+        .success();
+
+    let manifest = |name: &str| fs::read_to_string(td.join("crates").join(name).join("Cargo.toml"));
+    assert!(manifest("core")
+        .unwrap()
+        .contains("version = \"1.1.0\" # keep"));
+    assert!(manifest("cli")
+        .unwrap()
+        .contains("version = \"2.0.1\" # keep"));
+    let web = manifest("web").unwrap();
+    assert!(web.contains("version = \"0.3.0\" # keep"));
+    assert!(web.contains("core = { path = \"../core\", version = \"=1.1.0\" }"));
+    assert!(fs::read_to_string(td.join("Cargo.toml"))
+        .unwrap()
+        .contains("core = { path = \"crates/core\", version = \"1.1.0\" }"));
+}
+
+#[test]
+fn test_workspace_member_as_component() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    workspace(td);
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("cli")
+        .arg("next")
+        .arg("auto")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("2.0.1\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("core")
+        .arg("next")
+        .arg("auto")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("1.1.0\n"));
+}
+
+#[test]
+fn test_workspace_missing() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    add_commit(&repo, "Initial commit");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No Cargo workspace members found"));
+}
+
+#[test]
+fn test_workspace_untagged_member_continues_from_manifest() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let repo = workspace(td);
+    create_file(&td.join("crates").join("web"), "index.html", "");
+    add_all(&repo);
+    add_commit(&repo, "fix: render page");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("web")
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.3.0\n"));
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("--component")
+        .arg("web")
+        .arg("next")
+        .arg("auto")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff("0.3.1\n"));
+}
+
+#[test]
+fn test_workspace_write_twice() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    let repo = workspace(td);
+    create_file(&td.join("crates").join("web"), "index.html", "");
+    add_all(&repo);
+    add_commit(&repo, "fix: render page");
+
+    for _ in 0..2 {
+        Command::cargo_bin(env!("CARGO_PKG_NAME"))
+            .unwrap()
+            .current_dir(td)
+            .arg("workspace")
+            .arg("--write")
+            .assert()
+            .success();
+    }
+
+    let web = fs::read_to_string(td.join("crates").join("web").join("Cargo.toml")).unwrap();
+    assert!(web.contains("version = \"0.3.1\" # keep"));
+}
+
+#[test]
+fn test_workspace_write_keeps_compound_requirement() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    workspace(td);
+    create_crate(
+        td,
+        "web",
+        "0.3.0",
+        "[dev-dependencies]\ncore = { path = \"../core\", version = \">=0.9, <1.1\" }\n",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .arg("--write")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("not updated"));
+
+    let web = fs::read_to_string(td.join("crates").join("web").join("Cargo.toml")).unwrap();
+    assert!(web.contains("version = \">=0.9, <1.1\""));
+}
+
+#[test]
+fn test_workspace_write_keeps_upper_bound_requirement() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+    workspace(td);
+    create_crate(
+        td,
+        "web",
+        "0.3.0",
+        "[dev-dependencies]\ncore = { path = \"../core\", version = \"<2\" }\n",
+    );
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .arg("--write")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("not updated").not());
+
+    let web = fs::read_to_string(td.join("crates").join("web").join("Cargo.toml")).unwrap();
+    assert!(web.contains("version = \"<2\""));
+}
+
+#[test]
+fn test_workspace_write_inherited_version() {
+    let td = tempfile::tempdir().unwrap();
+    let td = td.path();
+
+    let repo = initialize_repository(td);
+    create_file(
+        td,
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.0.0\"\n",
+    );
+    for name in ["alpha", "beta"] {
+        let directory = td.join("crates").join(name);
+        fs::create_dir_all(&directory).unwrap();
+        create_file(
+            &directory,
+            "Cargo.toml",
+            &format!("[package]\nname = \"{}\"\nversion.workspace = true\n", name),
+        );
+    }
+    add_all(&repo);
+    add_commit(&repo, "Initial commit");
+    add_tag(&repo, "alpha-v1.0.0");
+    add_tag(&repo, "beta-v1.0.0");
+    create_file(&td.join("crates").join("alpha"), "lib.rs", "");
+    add_all(&repo);
+    add_commit(&repo, "feat: add alpha api");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .arg("--write")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Crates inheriting the workspace version must be released together",
+        ));
+
+    create_file(&td.join("crates").join("beta"), "lib.rs", "");
+    add_all(&repo);
+    add_commit(&repo, "feat: add beta api");
+
+    Command::cargo_bin(env!("CARGO_PKG_NAME"))
+        .unwrap()
+        .current_dir(td)
+        .arg("workspace")
+        .arg("--write")
+        .assert()
+        .success();
+
+    assert!(fs::read_to_string(td.join("Cargo.toml"))
+        .unwrap()
+        .contains("version = \"1.1.0\""));
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use git2::{Commit, IndexAddOption, Repository};
 use std::fs::File;
 use std::io::Write;
@@ -23,8 +25,8 @@ pub fn add_commit<'repo>(repo: &'repo Repository, message: &str) -> Commit<'repo
         Err(_) => None,
     };
     let mut parents = Vec::new();
-    if parent_commit.is_some() {
-        parents.push(parent_commit.as_ref().unwrap());
+    if let Some(parent) = &parent_commit {
+        parents.push(parent);
     }
 
     let signature = repo.signature().unwrap();
@@ -42,9 +44,7 @@ pub fn add_commit<'repo>(repo: &'repo Repository, message: &str) -> Commit<'repo
 
 pub fn add_all(repo: &Repository) {
     let mut index = repo.index().unwrap();
-    index
-        .add_all(&["."], IndexAddOption::DEFAULT, None)
-        .unwrap();
+    index.add_all(["."], IndexAddOption::DEFAULT, None).unwrap();
     index.write_tree().unwrap();
     index.write().unwrap();
 }
@@ -63,3 +63,64 @@ pub fn add_tag(repo: &Repository, tag_name: &str) {
 pub fn get_short_hash(commit: &Commit) -> String {
     commit.id().to_string()[..7].to_string()
 }
+
+/// Marks the repository as a shallow clone grafted at the given commit
+pub fn make_shallow(repo: &Repository, commit: &Commit) {
+    let mut file = File::create(repo.path().join("shallow")).unwrap();
+    writeln!(file, "{}", commit.id()).unwrap();
+}
+
+pub fn add_annotated_tag(repo: &Repository, tag_name: &str, message: &str) {
+    let obj = repo.revparse_single("HEAD").unwrap();
+    let signature = repo.signature().unwrap();
+    repo.tag(tag_name, &obj, &signature, message, false)
+        .unwrap();
+}
+
+/// Creates branch at HEAD and checks it out
+pub fn checkout_new_branch(repo: &Repository, branch_name: &str) {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch(branch_name, &head, false).unwrap();
+    repo.set_head(&format!("refs/heads/{}", branch_name))
+        .unwrap();
+}
+
+/// Creates a commit on top of `parent` without moving HEAD
+pub fn add_detached_commit<'repo>(
+    repo: &'repo Repository,
+    parent: &Commit,
+    message: &str,
+) -> Commit<'repo> {
+    let signature = repo.signature().unwrap();
+    let oid = repo
+        .commit(
+            None,
+            &signature,
+            &signature,
+            message,
+            &parent.tree().unwrap(),
+            &[parent],
+        )
+        .unwrap();
+    repo.find_commit(oid).unwrap()
+}
+
+/// Creates a merge commit of HEAD and `other` on HEAD
+pub fn add_merge_commit<'repo>(
+    repo: &'repo Repository,
+    other: &Commit,
+    message: &str,
+) -> Commit<'repo> {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let signature = repo.signature().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &head.tree().unwrap(),
+        &[&head, other],
+    )
+    .unwrap();
+    repo.head().unwrap().peel_to_commit().unwrap()
+}